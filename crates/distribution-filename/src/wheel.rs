@@ -69,6 +69,23 @@ impl WheelFilename {
         )
     }
 
+    /// Sort and deduplicate the tags, so that wheels with the same tags get the same filename,
+    /// e.g., `py2.py3` for both `py2.py3` and `py3.py2`.
+    ///
+    /// Parsed filenames keep the order of the original filename, this is for building wheels.
+    #[must_use]
+    pub fn with_canonical_tags(mut self) -> Self {
+        for tags in [
+            &mut self.python_tag,
+            &mut self.abi_tag,
+            &mut self.platform_tag,
+        ] {
+            tags.sort_unstable();
+            tags.dedup();
+        }
+        self
+    }
+
     /// Parse a wheel filename from the stem (e.g., `foo-1.2.3-py3-none-any`).
     pub fn from_stem(stem: &str) -> Result<Self, WheelFilenameError> {
         Self::parse(stem, stem)
@@ -331,4 +348,20 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn canonical_tags() {
+        let first = WheelFilename::from_str("foo-1.2.3-py3.py2-none-macosx_11_0_arm64.any.whl")
+            .unwrap()
+            .with_canonical_tags();
+        let second =
+            WheelFilename::from_str("foo-1.2.3-py2.py3.py3-none-any.macosx_11_0_arm64.whl")
+                .unwrap()
+                .with_canonical_tags();
+        assert_eq!(first, second);
+        assert_eq!(
+            first.to_string(),
+            "foo-1.2.3-py2.py3-none-any.macosx_11_0_arm64.whl"
+        );
+    }
 }
@@ -4,6 +4,7 @@ use std::path::Path;
 use distribution_filename::SourceDistExtension;
 use pep440_rs::Version;
 use pep508_rs::VerbatimUrl;
+use pypi_types::Yanked;
 use url::Url;
 use uv_git::GitUrl;
 
@@ -41,6 +42,17 @@ impl BuildableSource<'_> {
         }
     }
 
+    /// Returns `true` if the source is a registry distribution that was yanked.
+    pub fn is_yanked(&self) -> bool {
+        match self {
+            Self::Dist(SourceDist::Registry(dist)) => {
+                dist.file.yanked.as_ref().is_some_and(Yanked::is_yanked)
+            }
+            Self::Dist(_) => false,
+            Self::Url(_) => false,
+        }
+    }
+
     /// Return the [`BuildableSource`] as a [`SourceDist`], if it is a distribution.
     pub fn as_dist(&self) -> Option<&SourceDist> {
         match self {
@@ -269,7 +269,8 @@ impl InstalledDist {
             Err(err) => return Err(err.into()),
         };
         let direct_url = serde_json::from_reader::<fs_err::File, DirectUrl>(file)?;
-        Ok(Some(direct_url))
+        // Resolve URLs written relative to a base, so they compare equal to the requested URL.
+        Ok(Some(direct_url.resolve_base().unwrap_or(direct_url)))
     }
 
     /// Read the `uv_cache.json` file from a `.dist-info` directory.
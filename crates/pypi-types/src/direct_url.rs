@@ -2,8 +2,15 @@ use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use url::Url;
 
+#[derive(Debug, Error)]
+pub enum DirectUrlError {
+    #[error("Can't express `{url}` relative to `{base}`")]
+    NotRelative { url: String, base: String },
+}
+
 /// Metadata for a distribution that was installed via a direct URL.
 ///
 /// See: <https://packaging.python.org/en/latest/specifications/direct-url-data-structure/>
@@ -14,7 +21,13 @@ pub enum DirectUrl {
     /// ```json
     /// {"url": "file:///home/user/project", "dir_info": {}}
     /// ```
-    LocalDirectory { url: String, dir_info: DirInfo },
+    LocalDirectory {
+        url: String,
+        dir_info: DirInfo,
+        /// The base a relative `url` is resolved against, see [`DirectUrl::relative_to`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_url: Option<String>,
+    },
     /// The direct URL is a path to an archive. For example:
     /// ```json
     /// {"archive_info": {"hash": "sha256=75909db2664838d015e3d9139004ee16711748a52c8f336b52882266540215d8", "hashes": {"sha256": "75909db2664838d015e3d9139004ee16711748a52c8f336b52882266540215d8"}}, "url": "https://files.pythonhosted.org/packages/b8/8b/31273bf66016be6ad22bb7345c37ff350276cfd46e389a0c2ac5da9d9073/wheel-0.41.2-py3-none-any.whl"}
@@ -28,6 +41,9 @@ pub enum DirectUrl {
         archive_info: ArchiveInfo,
         #[serde(skip_serializing_if = "Option::is_none")]
         subdirectory: Option<PathBuf>,
+        /// The base a relative `url` is resolved against, see [`DirectUrl::relative_to`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_url: Option<String>,
     },
     /// The direct URL is path to a VCS repository. For example:
     /// ```json
@@ -41,6 +57,95 @@ pub enum DirectUrl {
     },
 }
 
+impl DirectUrl {
+    /// Rewrite a `file://` URL relative to `base`, a directory URL, e.g., for environments that are
+    /// moved between machines or containers.
+    ///
+    /// The base is recorded in `base_url`, so moving the environment only requires updating
+    /// `base_url`, and uv resolves the URL against it when reading `direct_url.json`. The direct
+    /// URL data structure requires absolute URLs, so other installers may not understand the
+    /// relative URL. VCS and remote URLs are returned unchanged.
+    pub fn relative_to(&self, base: &Url) -> Result<Self, DirectUrlError> {
+        let relative = |url: &str| {
+            let not_relative = || DirectUrlError::NotRelative {
+                url: url.to_string(),
+                base: base.to_string(),
+            };
+            let absolute = Url::parse(url).map_err(|_| not_relative())?;
+            if absolute.scheme() != "file" {
+                return Ok((url.to_string(), None));
+            }
+            let relative = base.make_relative(&absolute).ok_or_else(not_relative)?;
+            // Ensure that the relative URL is well-formed and resolves back to the absolute URL.
+            if base.join(&relative).ok().as_ref() != Some(&absolute) {
+                return Err(not_relative());
+            }
+            Ok((relative, Some(base.to_string())))
+        };
+        match self {
+            Self::LocalDirectory { url, dir_info, .. } => {
+                let (url, base_url) = relative(url)?;
+                Ok(Self::LocalDirectory {
+                    url,
+                    dir_info: dir_info.clone(),
+                    base_url,
+                })
+            }
+            Self::ArchiveUrl {
+                url,
+                archive_info,
+                subdirectory,
+                ..
+            } => {
+                let (url, base_url) = relative(url)?;
+                Ok(Self::ArchiveUrl {
+                    url,
+                    archive_info: archive_info.clone(),
+                    subdirectory: subdirectory.clone(),
+                    base_url,
+                })
+            }
+            Self::VcsUrl { .. } => Ok(self.clone()),
+        }
+    }
+
+    /// Resolve a URL written relative to `base_url` by [`DirectUrl::relative_to`] into an
+    /// absolute URL, so it can be compared to the requested URL.
+    pub fn resolve_base(&self) -> Result<Self, url::ParseError> {
+        match self {
+            Self::LocalDirectory {
+                url,
+                dir_info,
+                base_url: Some(base_url),
+            } => Ok(Self::LocalDirectory {
+                url: Url::parse(base_url)?.join(url)?.to_string(),
+                dir_info: dir_info.clone(),
+                base_url: None,
+            }),
+            Self::ArchiveUrl {
+                url,
+                archive_info,
+                subdirectory,
+                base_url: Some(base_url),
+            } => Ok(Self::ArchiveUrl {
+                url: Url::parse(base_url)?.join(url)?.to_string(),
+                archive_info: archive_info.clone(),
+                subdirectory: subdirectory.clone(),
+                base_url: None,
+            }),
+            _ => Ok(self.clone()),
+        }
+    }
+}
+
+/// Parse a URL, resolving it against the base if it was written relative to one.
+fn parse_url(url: &str, base_url: Option<&str>) -> Result<Url, url::ParseError> {
+    match base_url {
+        Some(base_url) => Url::parse(base_url)?.join(url),
+        None => Url::parse(url),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct DirInfo {
@@ -92,13 +197,14 @@ impl TryFrom<&DirectUrl> for Url {
 
     fn try_from(value: &DirectUrl) -> Result<Self, Self::Error> {
         match value {
-            DirectUrl::LocalDirectory { url, .. } => Self::parse(url),
+            DirectUrl::LocalDirectory { url, base_url, .. } => parse_url(url, base_url.as_deref()),
             DirectUrl::ArchiveUrl {
                 url,
                 subdirectory,
                 archive_info: _,
+                base_url,
             } => {
-                let mut url = Self::parse(url)?;
+                let mut url = parse_url(url, base_url.as_deref())?;
                 if let Some(subdirectory) = subdirectory {
                     url.set_fragment(Some(&format!("subdirectory={}", subdirectory.display())));
                 }
@@ -123,3 +229,64 @@ impl TryFrom<&DirectUrl> for Url {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::{DirInfo, DirectUrl};
+
+    fn editable(url: &str) -> DirectUrl {
+        DirectUrl::LocalDirectory {
+            url: url.to_string(),
+            dir_info: DirInfo {
+                editable: Some(true),
+            },
+            base_url: None,
+        }
+    }
+
+    #[test]
+    fn absolute() {
+        let direct_url = editable("file:///home/user/project");
+        assert_eq!(
+            Url::try_from(&direct_url).unwrap(),
+            Url::parse("file:///home/user/project").unwrap()
+        );
+    }
+
+    #[test]
+    fn relative_to() {
+        let direct_url = editable("file:///home/user/project");
+        let relative = |url: &str, base: &str| DirectUrl::LocalDirectory {
+            url: url.to_string(),
+            dir_info: DirInfo {
+                editable: Some(true),
+            },
+            base_url: Some(base.to_string()),
+        };
+
+        let base = Url::parse("file:///home/user/").unwrap();
+        assert_eq!(
+            direct_url.relative_to(&base).unwrap(),
+            relative("project", "file:///home/user/")
+        );
+        let base = Url::parse("file:///home/user/venvs/dev/").unwrap();
+        let relative_url = direct_url.relative_to(&base).unwrap();
+        assert_eq!(
+            relative_url,
+            relative("../../project", "file:///home/user/venvs/dev/")
+        );
+
+        // Relative URLs are resolved against their base when read back.
+        assert_eq!(
+            Url::try_from(&relative_url).unwrap(),
+            Url::parse("file:///home/user/project").unwrap()
+        );
+        assert_eq!(relative_url.resolve_base().unwrap(), direct_url);
+
+        // Remote URLs stay absolute.
+        let remote = editable("https://example.com/project");
+        assert_eq!(remote.relative_to(&base).unwrap(), remote);
+    }
+}
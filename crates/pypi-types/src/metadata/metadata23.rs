@@ -1,7 +1,10 @@
 //! Vendored from <https://github.com/PyO3/python-pkginfo-rs>
 
 use crate::metadata::Headers;
-use crate::MetadataError;
+use crate::{MetadataError, VerbatimParsedUrl};
+use pep508_rs::marker::MarkerValueExtra;
+use pep508_rs::{ExtraName, MarkerExpression, Requirement};
+use std::fmt::{Display, Write};
 use std::str;
 use std::str::FromStr;
 
@@ -152,6 +155,166 @@ impl Metadata23 {
             dynamic,
         })
     }
+
+    /// Convert to the pseudo-email format used by Python's METADATA.
+    ///
+    /// > The standard file format for metadata (including in wheels and installed projects) is
+    /// > based on the format of email headers. However, email formats have been revised several
+    /// > times, and exactly which email RFC applies to packaging metadata is not specified. In the
+    /// > absence of a precise definition, the practical standard is set by what the standard
+    /// > library `email.parser` module can parse using the `compat32` policy.
+    /// - <https://packaging.python.org/en/latest/specifications/core-metadata/#core-metadata-specifications>
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// Metadata-Version: 2.3
+    /// Name: hello-world
+    /// Version: 0.1.0
+    /// License: THE LICENSE
+    /// ```
+    pub fn core_metadata_format(&self) -> String {
+        self.format(false)
+    }
+
+    /// Like [`Metadata23::core_metadata_format`], but with the `Requires-Dist` entries grouped by
+    /// extra for readability: The base dependencies come first, then each `Provides-Extra`
+    /// followed by the dependencies of that extra.
+    ///
+    /// Only the order of the headers changes, parsing the result gives the same metadata.
+    /// Requirements that don't belong to exactly one of the provided extras are written with the
+    /// base dependencies.
+    pub fn core_metadata_format_grouped(&self) -> String {
+        self.format(true)
+    }
+
+    fn format(&self, group_extras: bool) -> String {
+        fn write_str(writer: &mut String, key: &str, value: impl Display) {
+            let value = value.to_string();
+            let mut lines = value.lines();
+            if let Some(line) = lines.next() {
+                let _ = writeln!(writer, "{key}: {line}");
+            } else {
+                // The value is an empty string
+                let _ = writeln!(writer, "{key}: ");
+            }
+            for line in lines {
+                // Python implementations vary
+                // https://github.com/pypa/pyproject-metadata/pull/150/files#diff-7d938dbc255a08c2cfab1b4f1f8d1f6519c9312dd0a39d7793fa778474f1fbd1L135-R141
+                let _ = writeln!(writer, "{}{}", " ".repeat(key.len() + 2), line);
+            }
+        }
+
+        fn write_opt_str(writer: &mut String, key: &str, value: Option<&impl Display>) {
+            if let Some(value) = value {
+                write_str(writer, key, value);
+            }
+        }
+
+        fn write_all(
+            writer: &mut String,
+            key: &str,
+            values: impl IntoIterator<Item = impl Display>,
+        ) {
+            for value in values {
+                write_str(writer, key, value);
+            }
+        }
+
+        let mut writer = String::new();
+        write_str(&mut writer, "Metadata-Version", &self.metadata_version);
+        write_str(&mut writer, "Name", &self.name);
+        write_str(&mut writer, "Version", &self.version);
+        write_all(&mut writer, "Platform", &self.platforms);
+        write_all(&mut writer, "Supported-Platform", &self.supported_platforms);
+        write_opt_str(&mut writer, "Summary", self.summary.as_ref());
+        write_opt_str(&mut writer, "Keywords", self.keywords.as_ref());
+        write_opt_str(&mut writer, "Home-Page", self.home_page.as_ref());
+        write_opt_str(&mut writer, "Download-URL", self.download_url.as_ref());
+        write_opt_str(&mut writer, "Author", self.author.as_ref());
+        write_opt_str(&mut writer, "Author-email", self.author_email.as_ref());
+        write_opt_str(&mut writer, "License", self.license.as_ref());
+        write_opt_str(
+            &mut writer,
+            "License-Expression",
+            self.license_expression.as_ref(),
+        );
+        write_all(&mut writer, "License-File", &self.license_files);
+        write_all(&mut writer, "Classifier", &self.classifiers);
+        if group_extras {
+            let mut extras: Vec<(&String, Vec<&String>)> = self
+                .provides_extras
+                .iter()
+                .map(|extra| (extra, Vec::new()))
+                .collect();
+            for requirement in &self.requires_dist {
+                let extra = requirement_extra(requirement).and_then(|extra| {
+                    extras.iter_mut().find(|(provided, _)| {
+                        ExtraName::from_str(provided).is_ok_and(|provided| provided == extra)
+                    })
+                });
+                if let Some((_, requirements)) = extra {
+                    requirements.push(requirement);
+                } else {
+                    write_str(&mut writer, "Requires-Dist", requirement);
+                }
+            }
+            for (extra, requirements) in extras {
+                write_str(&mut writer, "Provides-Extra", extra);
+                write_all(&mut writer, "Requires-Dist", requirements);
+            }
+        } else {
+            write_all(&mut writer, "Requires-Dist", &self.requires_dist);
+        }
+        write_all(&mut writer, "Provides-Dist", &self.provides_dist);
+        write_all(&mut writer, "Obsoletes-Dist", &self.obsoletes_dist);
+        write_opt_str(&mut writer, "Maintainer", self.maintainer.as_ref());
+        write_opt_str(
+            &mut writer,
+            "Maintainer-email",
+            self.maintainer_email.as_ref(),
+        );
+        write_opt_str(
+            &mut writer,
+            "Requires-Python",
+            self.requires_python.as_ref(),
+        );
+        write_all(&mut writer, "Requires-External", &self.requires_external);
+        write_all(&mut writer, "Project-URL", &self.project_urls);
+        if !group_extras {
+            write_all(&mut writer, "Provides-Extra", &self.provides_extras);
+        }
+        write_opt_str(
+            &mut writer,
+            "Description-Content-Type",
+            self.description_content_type.as_ref(),
+        );
+        write_all(&mut writer, "Dynamic", &self.dynamic);
+
+        // Some installers are sensitive to the trailing whitespace, so the headers always end
+        // with exactly one newline. The description is written as the author wrote it.
+        let headers_end = writer.trim_end().len();
+        writer.truncate(headers_end);
+        writer.push('\n');
+
+        if let Some(description) = &self.description {
+            writer.push('\n');
+            writer.push_str(description);
+        }
+        writer
+    }
+}
+
+/// The extra of a `Requires-Dist` entry, from its top level `extra == "..."` marker.
+fn requirement_extra(requirement: &str) -> Option<ExtraName> {
+    let requirement = Requirement::<VerbatimParsedUrl>::from_str(requirement).ok()?;
+    match requirement.marker.top_level_extra()? {
+        MarkerExpression::Extra {
+            name: MarkerValueExtra::Extra(extra),
+            ..
+        } => Some(extra),
+        _ => None,
+    }
 }
 
 impl FromStr for Metadata23 {
@@ -196,4 +359,28 @@ mod tests {
         assert_eq!(meta.author.as_deref(), Some("中文"));
         assert_eq!(meta.description.as_deref(), Some("一个 Python 包"));
     }
+
+    #[test]
+    fn core_metadata_format_trailing_newline() {
+        let metadata = Metadata23 {
+            metadata_version: "2.3".to_string(),
+            name: "asdf".to_string(),
+            version: "1.0".to_string(),
+            license: Some("MIT\n  \n".to_string()),
+            ..Metadata23::default()
+        };
+        assert_eq!(
+            metadata.core_metadata_format(),
+            "Metadata-Version: 2.3\nName: asdf\nVersion: 1.0\nLicense: MIT\n"
+        );
+
+        let metadata = Metadata23 {
+            description: Some("# asdf\n\n".to_string()),
+            ..metadata
+        };
+        assert_eq!(
+            metadata.core_metadata_format(),
+            "Metadata-Version: 2.3\nName: asdf\nVersion: 1.0\nLicense: MIT\n\n# asdf\n\n"
+        );
+    }
 }
@@ -414,6 +414,7 @@ impl TryFrom<&ParsedPathUrl> for DirectUrl {
                 hashes: None,
             },
             subdirectory: None,
+            base_url: None,
         })
     }
 }
@@ -427,6 +428,7 @@ impl TryFrom<&ParsedDirectoryUrl> for DirectUrl {
             dir_info: DirInfo {
                 editable: value.editable.then_some(true),
             },
+            base_url: None,
         })
     }
 }
@@ -442,6 +444,7 @@ impl TryFrom<&ParsedArchiveUrl> for DirectUrl {
                 hashes: None,
             },
             subdirectory: value.subdirectory.clone(),
+            base_url: None,
         })
     }
 }
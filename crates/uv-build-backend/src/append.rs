@@ -0,0 +1,199 @@
+//! Add a file to an already built wheel, updating its `RECORD` in place.
+
+use crate::installed::{check_record_entry, read_record, InstalledLayoutError};
+use crate::{hash_digest, Error};
+use std::io::{Read, Write};
+use std::path::Path;
+use tracing::debug;
+use uv_fs::Simplified;
+use zip::write::FileOptions;
+use zip::CompressionMethod;
+
+/// Add a file to a wheel, e.g., for post-processing, without rebuilding it.
+///
+/// The existing entries are copied unchanged, `RECORD` is removed, and the new file and an
+/// updated `RECORD` that lists it are appended. Before modifying the wheel, all entries are
+/// checked against the existing `RECORD`, so a wheel that was modified in another way is
+/// rejected instead of blessed with a new `RECORD`.
+pub fn append_to_wheel(wheel_path: &Path, path: &str, contents: &[u8]) -> Result<(), Error> {
+    let read_zip = |err| Error::ReadZip(wheel_path.to_path_buf(), err);
+    let modified = |err| Error::ModifiedWheel(wheel_path.to_path_buf(), err);
+
+    let mut archive = zip::ZipArchive::new(fs_err::File::open(wheel_path)?).map_err(read_zip)?;
+    let Some(record_path) = archive
+        .file_names()
+        .find(|name| {
+            name.split_once('/').is_some_and(|(directory, file)| {
+                directory.ends_with(".dist-info") && file == "RECORD"
+            })
+        })
+        .map(ToString::to_string)
+    else {
+        return Err(modified(InstalledLayoutError::MissingFile(
+            "*.dist-info/RECORD".to_string(),
+        )));
+    };
+    if archive.file_names().any(|name| name == path) {
+        return Err(Error::AppendExists(path.to_string()));
+    }
+
+    let mut record = Vec::new();
+    archive
+        .by_name(&record_path)
+        .map_err(read_zip)?
+        .read_to_end(&mut record)?;
+    let mut record_entries = read_record(&record, &record_path).map_err(modified)?;
+    record_entries.remove(&record_path);
+
+    // Check all files against `RECORD`, collecting the lines in archive order.
+    let mut lines = Vec::new();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(read_zip)?;
+        let name = file.name().to_string();
+        if file.is_dir() || name == record_path {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        let Some(record_entry) = record_entries.remove(&name) else {
+            return Err(modified(InstalledLayoutError::NotInRecord(name)));
+        };
+        check_record_entry(&record_entry, &bytes).map_err(modified)?;
+        lines.push(record_entry);
+    }
+    if let Some(missing) = record_entries.into_keys().next() {
+        return Err(modified(InstalledLayoutError::MissingRecordFile(missing)));
+    }
+
+    let mut new_record = csv::WriterBuilder::new()
+        .has_headers(false)
+        .escape(b'"')
+        .from_writer(Vec::new());
+    for line in &lines {
+        new_record.write_record([
+            line.path.as_str(),
+            line.hash.as_deref().unwrap_or_default(),
+            &line.size.map(|size| size.to_string()).unwrap_or_default(),
+        ])?;
+    }
+    new_record.write_record([path, &hash_digest(contents), &contents.len().to_string()])?;
+    new_record.write_record([record_path.as_str(), "", ""])?;
+    let new_record = new_record
+        .into_inner()
+        .map_err(|err| Error::Io(err.into_error()))?;
+
+    let wheel_dir = wheel_path.parent().unwrap_or(Path::new("."));
+    let temp_file = tempfile::Builder::new()
+        .prefix(".tmp-")
+        .suffix(".whl")
+        .tempfile_in(wheel_dir)?;
+    let write_zip = |err| Error::WriteZip(temp_file.path().to_path_buf(), err);
+    let mut writer = zip::ZipWriter::new(temp_file.as_file());
+    for index in 0..archive.len() {
+        let file = archive.by_index_raw(index).map_err(read_zip)?;
+        if file.name() == record_path {
+            continue;
+        }
+        writer.raw_copy_file(file).map_err(write_zip)?;
+    }
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+    debug!("Appending to wheel: `{path}`");
+    writer.start_file(path, options).map_err(write_zip)?;
+    writer.write_all(contents)?;
+    writer
+        .start_file(&record_path, options)
+        .map_err(write_zip)?;
+    writer.write_all(&new_record)?;
+    writer.finish().map_err(write_zip)?;
+    drop(writer);
+
+    debug!("Replacing wheel: `{}`", wheel_path.user_display());
+    temp_file
+        .into_temp_path()
+        .persist(wheel_path)
+        .map_err(|err| Error::Io(err.error))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build, BuildOptions};
+    use indoc::indoc;
+    use std::io;
+    use tempfile::TempDir;
+
+    /// Build the wheel of a minimal project.
+    async fn wheel(output: &Path) -> std::path::PathBuf {
+        let project = TempDir::new().unwrap();
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            indoc! {r#"
+                [project]
+                name = "foo"
+                version = "1.0.0"
+
+                [build-system]
+                requires = ["uv>=0.4.15,<5"]
+                build-backend = "uv"
+            "#},
+        )
+        .unwrap();
+        fs_err::create_dir_all(project.path().join("src/foo")).unwrap();
+        fs_err::write(project.path().join("src/foo/__init__.py"), "").unwrap();
+
+        let filename = build(project.path(), output, BuildOptions::default())
+            .await
+            .unwrap()
+            .filename;
+        output.join(filename.to_string())
+    }
+
+    #[tokio::test]
+    async fn append() {
+        let output = TempDir::new().unwrap();
+        let wheel = wheel(output.path()).await;
+
+        append_to_wheel(&wheel, "foo/py.typed", b"").unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(&wheel).unwrap()).unwrap();
+        let contents: Vec<_> = (0..archive.len())
+            .map(|index| archive.by_index(index).unwrap().name().to_string())
+            .collect();
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo/py.typed",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+        let record =
+            io::read_to_string(archive.by_name("foo-1.0.0.dist-info/RECORD").unwrap()).unwrap();
+        let lines: Vec<&str> = record.lines().collect();
+        assert_eq!(
+            lines[lines.len() - 2..],
+            [
+                "foo/py.typed,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0",
+                "foo-1.0.0.dist-info/RECORD,,"
+            ]
+        );
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn append_existing() {
+        let output = TempDir::new().unwrap();
+        let wheel = wheel(output.path()).await;
+
+        let err = append_to_wheel(&wheel, "foo/__init__.py", b"").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The wheel already contains `foo/__init__.py`"
+        );
+    }
+}
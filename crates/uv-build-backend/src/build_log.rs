@@ -0,0 +1,86 @@
+use jiff::Timestamp;
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+use tracing::debug;
+use uv_fs::Simplified;
+
+/// An event in the build log.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub(crate) enum BuildEvent {
+    /// The build started.
+    Start { source_tree: String },
+    /// The name, version and tags of the wheel, after reading `pyproject.toml`.
+    Resolved {
+        name: String,
+        version: String,
+        python_tag: Vec<String>,
+        abi_tag: Vec<String>,
+        platform_tag: Vec<String>,
+    },
+    /// A build step started, e.g., `write-sources`.
+    Step { name: &'static str },
+    /// A file was added to the wheel.
+    File { path: String, size: usize },
+    /// A warning was shown.
+    Warning { message: String },
+    /// The build finished, `error` is set if it failed.
+    ///
+    /// The sizes are set if the build succeeded: The sum of the uncompressed sizes of all
+    /// entries, and the size of the wheel file.
+    Finish {
+        filename: Option<String>,
+        error: Option<String>,
+        uncompressed_size: Option<u64>,
+        compressed_size: Option<u64>,
+    },
+}
+
+/// A line in the build log.
+#[derive(Debug, Serialize)]
+struct LogLine {
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    event: BuildEvent,
+}
+
+/// A JSON lines log of the build steps, for debugging builds in CI.
+///
+/// If the log is disabled, events are discarded.
+#[derive(Debug, Default)]
+pub(crate) struct BuildLog {
+    lines: Option<Vec<LogLine>>,
+}
+
+impl BuildLog {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            lines: enabled.then(Vec::new),
+        }
+    }
+
+    /// Record an event with the current time.
+    pub(crate) fn event(&mut self, event: BuildEvent) {
+        if let Some(lines) = &mut self.lines {
+            lines.push(LogLine {
+                timestamp: Timestamp::now(),
+                event,
+            });
+        }
+    }
+
+    /// Write the log as JSON lines, one event per line.
+    pub(crate) fn write(&self, path: &Path) -> io::Result<()> {
+        let Some(lines) = &self.lines else {
+            return Ok(());
+        };
+        let mut contents = String::new();
+        for line in lines {
+            contents.push_str(&serde_json::to_string(line).map_err(io::Error::other)?);
+            contents.push('\n');
+        }
+        debug!("Writing build log to: `{}`", path.user_display());
+        fs_err::write(path, contents)
+    }
+}
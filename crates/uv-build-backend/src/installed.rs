@@ -0,0 +1,376 @@
+//! Repackage an installed layout, i.e., a directory with the files of a package and an existing
+//! `.dist-info` directory, into a wheel.
+
+use crate::settings::CompressionLevel;
+use crate::{hash_digest, persist, AsyncDirectoryWrite, AsyncZipWriter, Error};
+use distribution_filename::WheelFilename;
+use pep440_rs::Version;
+use pypi_types::{Metadata23, MetadataError};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::debug;
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+use walkdir::WalkDir;
+
+#[derive(Debug, Error)]
+pub enum InstalledLayoutError {
+    #[error("No `.dist-info` directory in `{}`", _0.user_display())]
+    MissingDistInfo(PathBuf),
+    #[error("Multiple `.dist-info` directories in `{}`", _0.user_display())]
+    MultipleDistInfo(PathBuf),
+    #[error("`{0}` is missing")]
+    MissingFile(String),
+    #[error("Invalid `{0}`")]
+    Metadata(String, #[source] MetadataError),
+    #[error("`{0}` doesn't contain any `Tag` entries")]
+    MissingTags(String),
+    #[error("Invalid tag in `{0}`, expected `<python tag>-<abi tag>-<platform tag>`: `{1}`")]
+    InvalidTag(String, String),
+    #[error("Invalid `{0}`")]
+    Record(String, #[source] csv::Error),
+    #[error("File is not listed in `RECORD`: `{0}`")]
+    NotInRecord(String),
+    #[error("File listed in `RECORD` is missing: `{0}`")]
+    MissingRecordFile(String),
+    #[error("Hash mismatch for `{path}` in `RECORD`, expected `{expected}`, found `{actual}`")]
+    HashMismatch {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Size mismatch for `{path}` in `RECORD`, expected {expected} bytes, found {actual}")]
+    SizeMismatch {
+        path: String,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+/// A line in a `RECORD` file, e.g., `foo/__init__.py,sha256=<urlsafe base64>,42`.
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct RecordEntry {
+    pub(crate) path: String,
+    pub(crate) hash: Option<String>,
+    pub(crate) size: Option<u64>,
+}
+
+/// Zip an installed layout into a wheel in the output directory, keeping the existing
+/// `.dist-info` directory.
+///
+/// Instead of regenerating the metadata, `METADATA`, `WHEEL` and `RECORD` are validated: the
+/// filename is derived from `METADATA` and the `WHEEL` tags, and `RECORD` must list exactly the
+/// files in the layout, with matching hashes and sizes.
+pub async fn build_installed(
+    installed_dir: &Path,
+    wheel_dir: &Path,
+) -> Result<WheelFilename, Error> {
+    let dist_info_dir = find_dist_info(installed_dir)?;
+    let read_dist_info_file = |name: &str| {
+        let path = format!("{dist_info_dir}/{name}");
+        fs_err::read(installed_dir.join(&path)).map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                Error::from(InstalledLayoutError::MissingFile(path))
+            } else {
+                Error::from(err)
+            }
+        })
+    };
+
+    let metadata = read_dist_info_file("METADATA")?;
+    let metadata = Metadata23::parse(&metadata)
+        .map_err(|err| InstalledLayoutError::Metadata(format!("{dist_info_dir}/METADATA"), err))?;
+    let wheel = read_dist_info_file("WHEEL")?;
+    let filename = wheel_filename(&metadata, &String::from_utf8_lossy(&wheel), &dist_info_dir)?;
+    let record_path = format!("{dist_info_dir}/RECORD");
+    let record = read_dist_info_file("RECORD")?;
+
+    // Collect the files of the layout, with the `.dist-info` directory last and `RECORD` as the
+    // final entry.
+    let mut files = Vec::new();
+    let mut dist_info_files = Vec::new();
+    for entry in WalkDir::new(installed_dir).sort_by_file_name() {
+        let entry = entry.map_err(|err| Error::WalkDir {
+            root: installed_dir.to_path_buf(),
+            err,
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(installed_dir)
+            .expect("walkdir starts with root")
+            .portable_display()
+            .to_string();
+        if relative == record_path {
+            continue;
+        }
+        if relative.starts_with(&format!("{dist_info_dir}/")) {
+            dist_info_files.push((relative, entry.into_path()));
+        } else {
+            files.push((relative, entry.into_path()));
+        }
+    }
+    files.extend(dist_info_files);
+
+    let mut record_entries = read_record(&record, &record_path)?;
+    record_entries.remove(&record_path);
+
+    let temp_path = tempfile::Builder::new()
+        .prefix(".tmp-")
+        .suffix(".whl")
+        .tempfile_in(wheel_dir)?
+        .into_temp_path();
+    let wheel_file = fs_err::tokio::File::create(&*temp_path).await?;
+    let mut wheel_writer = AsyncZipWriter::new(wheel_file, CompressionLevel::default());
+    for (relative, path) in files {
+        let bytes = fs_err::tokio::read(&path).await?;
+        let Some(record_entry) = record_entries.remove(&relative) else {
+            return Err(InstalledLayoutError::NotInRecord(relative).into());
+        };
+        check_record_entry(&record_entry, &bytes)?;
+
+        debug!("Adding to wheel: `{relative}`");
+        wheel_writer.write_bytes(&relative, &bytes).await?;
+    }
+    if let Some(missing) = record_entries.into_keys().next() {
+        return Err(InstalledLayoutError::MissingRecordFile(missing).into());
+    }
+    wheel_writer.write_bytes(&record_path, &record).await?;
+    wheel_writer.close().await?;
+
+    persist(&temp_path, &wheel_dir.join(filename.to_string())).await?;
+    Ok(filename)
+}
+
+/// Find the single `.dist-info` directory at the root of the layout.
+fn find_dist_info(installed_dir: &Path) -> Result<String, Error> {
+    let mut dist_info_dirs = Vec::new();
+    for entry in fs_err::read_dir(installed_dir)? {
+        let entry = entry?;
+        let Some(name) = entry.file_name().to_str().map(ToString::to_string) else {
+            continue;
+        };
+        if entry.file_type()?.is_dir() && name.ends_with(".dist-info") {
+            dist_info_dirs.push(name);
+        }
+    }
+    match dist_info_dirs.as_slice() {
+        [] => Err(InstalledLayoutError::MissingDistInfo(installed_dir.to_path_buf()).into()),
+        [dist_info_dir] => Ok(dist_info_dir.clone()),
+        _ => Err(InstalledLayoutError::MultipleDistInfo(installed_dir.to_path_buf()).into()),
+    }
+}
+
+/// Derive the wheel filename from the name and version in `METADATA` and the `Tag` entries in
+/// `WHEEL`, e.g., `Tag: py2-none-any` and `Tag: py3-none-any` become `py2.py3-none-any`.
+fn wheel_filename(
+    metadata: &Metadata23,
+    wheel: &str,
+    dist_info_dir: &str,
+) -> Result<WheelFilename, Error> {
+    let metadata_path = || format!("{dist_info_dir}/METADATA");
+    let name = PackageName::from_str(&metadata.name).map_err(|err| {
+        InstalledLayoutError::Metadata(metadata_path(), MetadataError::InvalidName(err))
+    })?;
+    let version = Version::from_str(&metadata.version).map_err(|err| {
+        InstalledLayoutError::Metadata(metadata_path(), MetadataError::Pep440VersionError(err))
+    })?;
+
+    let wheel_path = format!("{dist_info_dir}/WHEEL");
+    let mut python_tag: Vec<String> = Vec::new();
+    let mut abi_tag: Vec<String> = Vec::new();
+    let mut platform_tag: Vec<String> = Vec::new();
+    for line in wheel.lines() {
+        let Some(tag) = line.strip_prefix("Tag:") else {
+            continue;
+        };
+        let tag = tag.trim();
+        let mut parts = tag.split('-');
+        let (Some(python), Some(abi), Some(platform), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(InstalledLayoutError::InvalidTag(wheel_path, tag.to_string()).into());
+        };
+        for (tags, tag) in [
+            (&mut python_tag, python),
+            (&mut abi_tag, abi),
+            (&mut platform_tag, platform),
+        ] {
+            if !tags.iter().any(|existing| existing == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+    if python_tag.is_empty() {
+        return Err(InstalledLayoutError::MissingTags(wheel_path).into());
+    }
+
+    Ok(WheelFilename {
+        name,
+        version,
+        build_tag: None,
+        python_tag,
+        abi_tag,
+        platform_tag,
+    }
+    .with_canonical_tags())
+}
+
+/// Parse a `RECORD` file into its entries, by path.
+pub(crate) fn read_record(
+    record: &[u8],
+    record_path: &str,
+) -> Result<BTreeMap<String, RecordEntry>, InstalledLayoutError> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .escape(Some(b'"'))
+        .from_reader(record)
+        .deserialize()
+        .map(|entry| {
+            let entry: RecordEntry =
+                entry.map_err(|err| InstalledLayoutError::Record(record_path.to_string(), err))?;
+            Ok((entry.path.clone(), entry))
+        })
+        .collect()
+}
+
+/// Check that the file matches the hash and size in its `RECORD` entry, if they are recorded.
+pub(crate) fn check_record_entry(
+    record_entry: &RecordEntry,
+    bytes: &[u8],
+) -> Result<(), InstalledLayoutError> {
+    if let Some(expected) = &record_entry.hash {
+        let actual = hash_digest(bytes);
+        if *expected != actual {
+            return Err(InstalledLayoutError::HashMismatch {
+                path: record_entry.path.clone(),
+                expected: expected.clone(),
+                actual,
+            });
+        }
+    }
+    if let Some(expected) = record_entry.size {
+        let actual = bytes.len() as u64;
+        if expected != actual {
+            return Err(InstalledLayoutError::SizeMismatch {
+                path: record_entry.path.clone(),
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use tempfile::TempDir;
+
+    /// A synthetic installed layout of `foo` 1.0.0 with a matching `RECORD`.
+    fn installed_layout() -> TempDir {
+        let installed = TempDir::new().unwrap();
+        let files = [
+            ("foo/__init__.py", "x = 1\n"),
+            (
+                "foo-1.0.0.dist-info/METADATA",
+                "Metadata-Version: 2.3\nName: foo\nVersion: 1.0.0\n",
+            ),
+            (
+                "foo-1.0.0.dist-info/WHEEL",
+                indoc! {"
+                    Wheel-Version: 1.0
+                    Generator: test
+                    Root-Is-Purelib: true
+                    Tag: py2-none-any
+                    Tag: py3-none-any
+                "},
+            ),
+        ];
+        let mut record = String::new();
+        for (path, contents) in files {
+            let full_path = installed.path().join(path);
+            fs_err::create_dir_all(full_path.parent().unwrap()).unwrap();
+            fs_err::write(full_path, contents).unwrap();
+            record.push_str(&format!(
+                "{path},{},{}\n",
+                hash_digest(contents.as_bytes()),
+                contents.len()
+            ));
+        }
+        record.push_str("foo-1.0.0.dist-info/RECORD,,\n");
+        fs_err::write(installed.path().join("foo-1.0.0.dist-info/RECORD"), record).unwrap();
+        installed
+    }
+
+    #[tokio::test]
+    async fn repackage() {
+        let installed = installed_layout();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_installed(installed.path(), output.path())
+            .await
+            .unwrap();
+        assert_eq!(filename.to_string(), "foo-1.0.0-py2.py3-none-any.whl");
+
+        let wheel = fs_err::File::open(output.path().join(filename.to_string())).unwrap();
+        let mut archive = zip::ZipArchive::new(wheel).unwrap();
+        let contents: Vec<_> = (0..archive.len())
+            .map(|index| archive.by_index(index).unwrap().name().to_string())
+            .collect();
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn hash_mismatch() {
+        let installed = installed_layout();
+        fs_err::write(installed.path().join("foo/__init__.py"), "x = 2\n").unwrap();
+        let output = TempDir::new().unwrap();
+
+        let err = build_installed(installed.path(), output.path())
+            .await
+            .unwrap_err();
+        match err {
+            Error::InstalledLayout(InstalledLayoutError::HashMismatch {
+                path, expected, ..
+            }) => {
+                assert_eq!(path, "foo/__init__.py");
+                assert_eq!(
+                    expected,
+                    "sha256=nia_NpkRxFwkPGhBR7I_yeHc_PJX0pmhxjIBam_NM_Q"
+                );
+            }
+            err => panic!("Expected a hash mismatch, found: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn unrecorded_file() {
+        let installed = installed_layout();
+        fs_err::write(installed.path().join("foo/extra.py"), "").unwrap();
+        let output = TempDir::new().unwrap();
+
+        let err = build_installed(installed.path(), output.path())
+            .await
+            .unwrap_err();
+        match err {
+            Error::InstalledLayout(InstalledLayoutError::NotInRecord(path)) => {
+                assert_eq!(path, "foo/extra.py");
+            }
+            err => panic!("Expected an unrecorded file, found: {err}"),
+        }
+    }
+}
@@ -0,0 +1,5345 @@
+mod append;
+mod build_log;
+mod installed;
+mod manifest;
+mod metadata;
+mod pep639_glob;
+mod settings;
+
+pub use append::append_to_wheel;
+pub use installed::{build_installed, InstalledLayoutError};
+pub use manifest::ManifestError;
+pub use metadata::ValidationError;
+pub use pep639_glob::Pep639GlobError;
+
+use crate::build_log::{BuildEvent, BuildLog};
+use crate::installed::read_record;
+use crate::manifest::Manifest;
+use crate::metadata::{apply_overlay, is_identifier, PyProjectToml};
+use crate::settings::{
+    CompressionLevel, FileModes, LicenseDir, NonUtf8Filenames, RecordHashEncoding, Symlinks,
+};
+use async_compression::tokio::write::GzipEncoder;
+use async_zip::base::write::ZipFileWriter;
+use async_zip::error::ZipError;
+use async_zip::{
+    AttributeCompatibility, Compression, DeflateOption, ZipDateTime, ZipDateTimeBuilder,
+    ZipEntryBuilder, ZipString,
+};
+use distribution_filename::{
+    SourceDistExtension, SourceDistFilename, WheelFilename, WheelFilenameError,
+};
+use futures::StreamExt;
+use glob::{GlobError, PatternError};
+use jiff::tz::TimeZone;
+use jiff::Timestamp;
+use pypi_types::{Metadata23, MetadataError};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+use walkdir::WalkDir;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Invalid pyproject.toml")]
+    Toml(#[from] toml::de::Error),
+    #[error("Invalid pyproject.toml")]
+    Validation(#[from] ValidationError),
+    #[error("Invalid `project.license-files` glob expression: `{0}`")]
+    Pep639Glob(String, #[source] Pep639GlobError),
+    #[error("The `project.license-files` entry is not a valid glob pattern: `{0}`")]
+    Pattern(String, #[source] PatternError),
+    #[error("The `tool.uv.build-backend.{setting}` entry is not a valid glob pattern: `{glob}`")]
+    SourceGlob {
+        setting: &'static str,
+        glob: String,
+        #[source]
+        err: PatternError,
+    },
+    /// [`GlobError`] is a wrapped io error.
+    #[error(transparent)]
+    Glob(#[from] GlobError),
+    #[error("Failed to walk source tree: `{}`", root.user_display())]
+    WalkDir {
+        root: PathBuf,
+        #[source]
+        err: walkdir::Error,
+    },
+    #[error("Missing module directory: `{}`", _0.user_display())]
+    MissingModule(PathBuf),
+    #[error("`tool.uv.build-backend.{setting}` must be a mode between `0o400` and `0o777` that the owner can {access}, not `0o{mode:o}`")]
+    InvalidFileMode {
+        setting: &'static str,
+        access: &'static str,
+        mode: u16,
+    },
+    #[error("`tool.uv.build-backend.namespace` must be a dotted module name such as `acme.plugin`, not `{0}`")]
+    InvalidNamespace(String),
+    #[error("Invalid config setting, expected `<key>=<value>`: `{0}`")]
+    InvalidConfigSetting(String),
+    #[error("Unknown config setting, `tool.uv.build-backend` has no setting `{0}`")]
+    UnknownConfigSetting(String),
+    #[error("Failed to write wheel zip archive")]
+    Zip(#[from] ZipError),
+    #[error("Failed to run `{PYTHON}` to check the syntax of the Python files")]
+    PythonCheck(#[source] io::Error),
+    #[error("Syntax error in `{}` on line {line}: {message}", path.user_display())]
+    Syntax {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    #[error("Expected a `{expected}` directory in the metadata directory `{}`", metadata_directory.user_display())]
+    MissingDistInfo {
+        metadata_directory: PathBuf,
+        expected: String,
+    },
+    #[error("The `RECORD` in the metadata directory doesn't match the packaged files, the metadata is outdated: `{}`", _0.user_display())]
+    StaleRecord(PathBuf, #[source] InstalledLayoutError),
+    #[error("The output file already exists: `{}`", _0.user_display())]
+    OutputExists(PathBuf),
+    #[error("Invalid installed layout")]
+    InstalledLayout(#[from] InstalledLayoutError),
+    #[error("Failed to write RECORD")]
+    Csv(#[from] csv::Error),
+    #[error("Source root of the editable is not a directory: `{}`", _0.user_display())]
+    MissingSourceRoot(PathBuf),
+    #[error("Source root of the editable is not valid UTF-8: `{}`", _0.user_display())]
+    NonUtf8SourceRoot(PathBuf),
+    #[error("Failed to read wheel zip archive: `{}`", _0.user_display())]
+    ReadZip(PathBuf, #[source] zip::result::ZipError),
+    #[error("Invalid line in `entry_points.txt`: `{0}`")]
+    EntryPoints(String),
+    #[error("Invalid `tool.uv.build-backend.entry-points-file`: `{}`", _0.user_display())]
+    EntryPointsFile(PathBuf, #[source] Box<Error>),
+    #[error("Invalid `tool.uv.build-backend.manifest`: `{}`", _0.user_display())]
+    Manifest(PathBuf, #[source] ManifestError),
+    #[error("File names in wheels must be valid UTF-8: `{}`", _0.user_display())]
+    NonUtf8Path(PathBuf),
+    #[error("The built wheel has an invalid filename: `{0}`")]
+    InvalidWheelFilename(String, #[source] WheelFilenameError),
+    #[error("The built wheel has a filename that doesn't round-trip: `{0}`")]
+    NonCanonicalWheelFilename(String),
+    #[error("Symlinks are not allowed by `tool.uv.build-backend.symlinks`: `{}`", _0.user_display())]
+    Symlink(PathBuf),
+    #[error("Symlink cycle in the module: `{}`", _0.user_display())]
+    SymlinkCycle(PathBuf),
+    #[error("The module is nested deeper than `tool.uv.build-backend.max-walk-depth = {max_depth}`: `{}`", path.user_display())]
+    MaxWalkDepth { path: PathBuf, max_depth: usize },
+    #[error("Failed to write wheel zip archive: `{}`", _0.user_display())]
+    WriteZip(PathBuf, #[source] zip::result::ZipError),
+    #[error("The wheel doesn't match its RECORD, it was modified after it was built: `{}`", _0.user_display())]
+    ModifiedWheel(PathBuf, #[source] InstalledLayoutError),
+    #[error("The wheel already contains `{0}`")]
+    AppendExists(String),
+    #[error("Expected {expected} bytes for `{path}`, but read {actual} bytes, was the file modified during the build?")]
+    SizeMismatch {
+        path: String,
+        expected: u64,
+        actual: u64,
+    },
+    #[error("Failed to extract source distribution: `{}`", _0.user_display())]
+    Extract(PathBuf, #[source] uv_extract::Error),
+    #[error("Invalid source distribution `{}`: {1}", _0.user_display())]
+    InvalidSdist(PathBuf, String),
+    #[error("Invalid `PKG-INFO` in source distribution: `{}`", _0.user_display())]
+    SdistMetadata(PathBuf, #[source] MetadataError),
+    #[error("Stub-only package doesn't contain any `.pyi` files: `{}`", _0.user_display())]
+    MissingStubs(PathBuf),
+    #[error("Invalid destination for `{file}` in `tool.uv.build-backend.data-files`, expected `{{<category>}}/<path>` with a category out of {}: `{destination}`", DATA_CATEGORIES.iter().map(|category| format!("`{category}`")).collect::<Vec<_>>().join(", "))]
+    InvalidDataDestination { file: String, destination: String },
+    #[error("Invalid category in `tool.uv.build-backend.data`, expected one of {}: `{0}`", DATA_CATEGORIES.iter().map(|category| format!("`{category}`")).collect::<Vec<_>>().join(", "))]
+    InvalidDataCategory(String),
+    #[error("The `tool.uv.build-backend.data` globs must be relative to the project root: `{0}`")]
+    DataGlob(String),
+    #[error("The `tool.uv.build-backend.{setting}` glob `{glob}` matches a file outside the project directory: `{}`", path.user_display())]
+    GlobOutsideProject {
+        setting: &'static str,
+        glob: String,
+        path: PathBuf,
+    },
+    #[error("The wheel is tagged for any platform, but contains a compiled extension: `{}`", _0.user_display())]
+    CompiledExtension(PathBuf),
+    #[error("The semaphore limiting concurrent builds was closed")]
+    SemaphoreClosed,
+}
+
+/// What to do when the output directory already contains the file to build.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputPolicy {
+    /// Replace the existing file.
+    #[default]
+    Overwrite,
+    /// Fail with [`Error::OutputExists`].
+    Error,
+    /// Keep the existing file and return its name without building.
+    Skip,
+}
+
+/// The optional settings of [`build`], e.g.,
+/// `BuildOptions { output_policy: OutputPolicy::Skip, ..BuildOptions::default() }`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuildOptions<'a> {
+    /// A metadata directory previously written by [`metadata`], whose `.dist-info` directory is
+    /// copied into the wheel instead of generating the metadata again, as in PEP 517's
+    /// `build_wheel`.
+    pub metadata_directory: Option<&'a Path>,
+    /// What to do when the output directory already contains the wheel.
+    pub output_policy: OutputPolicy,
+    /// The directory for the temporary file the wheel is written to before it is moved into
+    /// place, defaulting to the output directory.
+    pub temp_dir: Option<&'a Path>,
+    /// Fields added to the generated core metadata, taking precedence over `pyproject.toml`,
+    /// e.g., `("Classifier", "Private :: Do Not Upload")`.
+    ///
+    /// Multiple-use fields are appended to, single-use fields are replaced. With a metadata
+    /// directory, the copied `METADATA` is used as is, so pass the same overlay to [`metadata`].
+    pub metadata_overlay: &'a [(String, String)],
+}
+
+/// Allow dispatching between writing to a directory, writing to zip and writing to a `.tar.gz`.
+trait AsyncDirectoryWrite: Sized {
+    /// Add a file with the given content.
+    ///
+    /// The path is relative to the archive root and uses `/` as separator.
+    async fn write_bytes(&mut self, path: &str, bytes: &[u8]) -> Result<(), Error>;
+
+    /// Add an executable file, e.g., a script for the `.data/scripts` directory.
+    async fn write_executable(&mut self, path: &str, bytes: &[u8]) -> Result<(), Error> {
+        self.write_bytes(path, bytes).await
+    }
+
+    /// Add a file from a reader that must deliver exactly `size` bytes, e.g., the length of the
+    /// file on disk.
+    ///
+    /// A reader that delivers fewer or more bytes, e.g., for a file that was truncated while
+    /// reading it, is an error, so `RECORD` never lists a size that doesn't match the metadata of
+    /// the source file. Reading stops after one byte more than `size`.
+    async fn write_reader(
+        &mut self,
+        path: &str,
+        reader: &mut (impl AsyncRead + Unpin),
+        size: u64,
+    ) -> Result<(), Error> {
+        let mut bytes = Vec::with_capacity(usize::try_from(size).unwrap_or_default());
+        reader
+            .take(size.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .await?;
+        let actual = bytes.len() as u64;
+        if actual != size {
+            return Err(Error::SizeMismatch {
+                path: path.to_string(),
+                expected: size,
+                actual,
+            });
+        }
+        self.write_bytes(path, &bytes).await
+    }
+
+    #[allow(clippy::unused_async)] // https://github.com/rust-lang/rust-clippy/issues/11660
+    async fn close(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Zip archive (wheel) writer.
+///
+/// Tracks the hash and size of each file for `RECORD`.
+struct AsyncZipWriter {
+    writer: ZipFileWriter<tokio_util::compat::Compat<fs_err::tokio::File>>,
+    compression_level: CompressionLevel,
+    /// The permissions of all entries.
+    file_modes: FileModes,
+    /// The modification time of all entries.
+    modification_time: ZipDateTime,
+    record: Vec<RecordEntry>,
+}
+
+/// A file written to the wheel, listed in `RECORD`.
+struct RecordEntry {
+    /// The path inside the wheel, with `/` as separator.
+    path: String,
+    /// The SHA-256 digest of the file.
+    hash: Vec<u8>,
+    /// The size of the file in bytes.
+    size: usize,
+}
+
+impl AsyncZipWriter {
+    fn new(file: fs_err::tokio::File, compression_level: CompressionLevel) -> Self {
+        Self {
+            writer: ZipFileWriter::with_tokio(file),
+            compression_level,
+            file_modes: FileModes::default(),
+            modification_time: zip_date_time(source_date_epoch()),
+            record: Vec::new(),
+        }
+    }
+
+    /// Use other permissions than `0o644` for regular files and `0o755` for executables.
+    #[must_use]
+    fn with_file_modes(self, file_modes: FileModes) -> Self {
+        Self { file_modes, ..self }
+    }
+
+    /// Write `RECORD` as final entry, listing all previously written files.
+    ///
+    /// The `RECORD` file itself is listed without hash and size.
+    ///
+    /// <https://packaging.python.org/en/latest/specifications/binary-distribution-format/#the-dist-info-directory>
+    async fn write_record(
+        &mut self,
+        dist_info_dir: &str,
+        encoding: RecordHashEncoding,
+    ) -> Result<(), Error> {
+        let record_path = format!("{dist_info_dir}/RECORD");
+        let mut record = csv::WriterBuilder::new()
+            .has_headers(false)
+            .escape(b'"')
+            .from_writer(Vec::new());
+        for entry in &self.record {
+            record.write_record([
+                entry.path.as_str(),
+                &encoding.format(&entry.hash),
+                &entry.size.to_string(),
+            ])?;
+        }
+        record.write_record([record_path.as_str(), "", ""])?;
+        let record = record
+            .into_inner()
+            .map_err(|err| Error::Io(err.into_error()))?;
+        self.write_bytes(&record_path, &record).await
+    }
+}
+
+impl AsyncZipWriter {
+    /// The zip entry for a file, with the compression, permissions and modification time of the
+    /// wheel.
+    fn entry_builder(&self, path: &str, mode: u16) -> ZipEntryBuilder {
+        let path_string = ZipString::from(path.to_string());
+        let entry = match self.compression_level {
+            CompressionLevel::Stored => ZipEntryBuilder::new(path_string, Compression::Stored),
+            CompressionLevel::Fast => ZipEntryBuilder::new(path_string, Compression::Deflate)
+                .deflate_option(DeflateOption::Fast),
+            CompressionLevel::Default => ZipEntryBuilder::new(path_string, Compression::Deflate),
+            CompressionLevel::Best => ZipEntryBuilder::new(path_string, Compression::Deflate)
+                .deflate_option(DeflateOption::Maximum),
+        };
+        entry
+            // Always claim a Unix host, so wheels built on Windows and Unix are identical.
+            .attribute_compatibility(AttributeCompatibility::Unix)
+            // https://github.com/Majored/rs-async-zip/issues/150
+            .unix_permissions(mode)
+            .last_modification_date(self.modification_time.clone())
+    }
+
+    async fn write_entry(&mut self, path: &str, bytes: &[u8], mode: u16) -> Result<(), Error> {
+        let entry = self.entry_builder(path, mode);
+        self.writer.write_entry_whole(entry, bytes).await?;
+        self.record.push(RecordEntry {
+            path: path.to_string(),
+            hash: Sha256::digest(bytes).to_vec(),
+            size: bytes.len(),
+        });
+        Ok(())
+    }
+}
+
+impl AsyncDirectoryWrite for AsyncZipWriter {
+    async fn write_bytes(&mut self, path: &str, bytes: &[u8]) -> Result<(), Error> {
+        self.write_entry(path, bytes, self.file_modes.file).await
+    }
+
+    /// Stream the file into the zip entry, hashing and counting the bytes as they are copied.
+    ///
+    /// Fails as soon as the reader delivers more than `size` bytes, or if it ends early.
+    async fn write_reader(
+        &mut self,
+        path: &str,
+        reader: &mut (impl AsyncRead + Unpin),
+        size: u64,
+    ) -> Result<(), Error> {
+        let size_mismatch = |actual: usize| Error::SizeMismatch {
+            path: path.to_string(),
+            expected: size,
+            actual: actual as u64,
+        };
+        let entry = self.entry_builder(path, self.file_modes.file);
+        let mut entry_writer = self.writer.write_entry_stream(entry).await?;
+        let mut hasher = Sha256::new();
+        let mut actual = 0;
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            let read = reader.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            actual += read;
+            if actual as u64 > size {
+                return Err(size_mismatch(actual));
+            }
+            hasher.update(&buffer[..read]);
+            futures::AsyncWriteExt::write_all(&mut entry_writer, &buffer[..read]).await?;
+        }
+        if actual as u64 != size {
+            return Err(size_mismatch(actual));
+        }
+        entry_writer.close().await?;
+        self.record.push(RecordEntry {
+            path: path.to_string(),
+            hash: hasher.finalize().to_vec(),
+            size: actual,
+        });
+        Ok(())
+    }
+
+    async fn write_executable(&mut self, path: &str, bytes: &[u8]) -> Result<(), Error> {
+        self.write_entry(path, bytes, self.file_modes.executable)
+            .await
+    }
+
+    async fn close(self) -> Result<(), Error> {
+        self.writer.close().await?.into_inner().flush().await?;
+        Ok(())
+    }
+}
+
+/// The earliest time zip archives can represent, 1980-01-01, which is also the modification time
+/// of all archive entries without `SOURCE_DATE_EPOCH`.
+const ZIP_EPOCH: i64 = 315_532_800;
+
+/// The latest time zip archives can represent, 2107-12-31 23:59:58.
+const ZIP_END: i64 = 4_354_819_198;
+
+/// The modification time of the files in built archives, as Unix timestamp.
+///
+/// Archives don't use the current time, so that building the same sources twice produces the
+/// same bytes. Distributions can set a time with `SOURCE_DATE_EPOCH`, see
+/// <https://reproducible-builds.org/specs/source-date-epoch/>.
+fn source_date_epoch() -> i64 {
+    let Some(value) = std::env::var_os("SOURCE_DATE_EPOCH") else {
+        return ZIP_EPOCH;
+    };
+    if let Some(epoch) = value.to_str().and_then(|value| value.trim().parse().ok()) {
+        epoch
+    } else {
+        debug!(
+            "Ignoring invalid `SOURCE_DATE_EPOCH`: `{}`",
+            value.to_string_lossy()
+        );
+        ZIP_EPOCH
+    }
+}
+
+/// Convert a Unix timestamp to a zip timestamp in UTC, clamped to the range zip can represent.
+fn zip_date_time(epoch: i64) -> ZipDateTime {
+    let date_time = Timestamp::from_second(epoch.clamp(ZIP_EPOCH, ZIP_END))
+        .expect("zip timestamps are in range")
+        .to_zoned(TimeZone::UTC);
+    ZipDateTimeBuilder::new()
+        .year(i32::from(date_time.year()))
+        .month(u32::from(date_time.month().unsigned_abs()))
+        .day(u32::from(date_time.day().unsigned_abs()))
+        .hour(u32::from(date_time.hour().unsigned_abs()))
+        .minute(u32::from(date_time.minute().unsigned_abs()))
+        .second(u32::from(date_time.second().unsigned_abs()))
+        .build()
+}
+
+/// Gzip-compressed tar writer for source distributions.
+struct AsyncTarGzWriter {
+    builder: tokio_tar::Builder<GzipEncoder<fs_err::tokio::File>>,
+    /// The permissions of all files.
+    file_modes: FileModes,
+    /// The modification time of all files.
+    mtime: u64,
+}
+
+impl AsyncTarGzWriter {
+    fn new(file: fs_err::tokio::File, file_modes: FileModes) -> Self {
+        // Wheels built from the extracted files need timestamps that zip can represent.
+        let mtime = source_date_epoch().max(ZIP_EPOCH);
+        Self {
+            builder: tokio_tar::Builder::new(GzipEncoder::new(file)),
+            file_modes,
+            mtime: u64::try_from(mtime).expect("the timestamp is positive"),
+        }
+    }
+}
+
+impl AsyncTarGzWriter {
+    async fn write_entry(&mut self, path: &str, bytes: &[u8], mode: u32) -> Result<(), Error> {
+        let mut header = tokio_tar::Header::new_gnu();
+        header.set_entry_type(tokio_tar::EntryType::Regular);
+        header.set_size(bytes.len() as u64);
+        header.set_mode(mode);
+        header.set_mtime(self.mtime);
+        self.builder.append_data(&mut header, path, bytes).await?;
+        Ok(())
+    }
+}
+
+impl AsyncDirectoryWrite for AsyncTarGzWriter {
+    async fn write_bytes(&mut self, path: &str, bytes: &[u8]) -> Result<(), Error> {
+        self.write_entry(path, bytes, u32::from(self.file_modes.file))
+            .await
+    }
+
+    async fn write_executable(&mut self, path: &str, bytes: &[u8]) -> Result<(), Error> {
+        self.write_entry(path, bytes, u32::from(self.file_modes.executable))
+            .await
+    }
+
+    async fn close(self) -> Result<(), Error> {
+        let mut encoder = self.builder.into_inner().await?;
+        encoder.shutdown().await?;
+        Ok(())
+    }
+}
+
+/// File system writer.
+struct AsyncFsWriter {
+    root: PathBuf,
+}
+
+impl AsyncDirectoryWrite for AsyncFsWriter {
+    async fn write_bytes(&mut self, path: &str, bytes: &[u8]) -> Result<(), Error> {
+        let path = self.root.join(path);
+        if let Some(parent) = path.parent() {
+            fs_err::tokio::create_dir_all(parent).await?;
+        }
+        fs_err::tokio::write(path, bytes).await?;
+        Ok(())
+    }
+
+    async fn write_executable(&mut self, path: &str, bytes: &[u8]) -> Result<(), Error> {
+        self.write_bytes(path, bytes).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs_err::tokio::set_permissions(
+                self.root.join(path),
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Build a wheel from the source tree and place it in the output directory.
+///
+/// The wheel is first written to a temporary file and then moved into place, so an interrupted
+/// build never leaves a truncated wheel behind. See [`BuildOptions`] for the optional settings.
+///
+/// The result lists the packaged files, so callers can report the contents without reading the
+/// wheel again.
+pub async fn build(
+    source_tree: &Path,
+    wheel_dir: &Path,
+    options: BuildOptions<'_>,
+) -> Result<BuildResult, Error> {
+    let contents = fs_err::tokio::read_to_string(source_tree.join("pyproject.toml")).await?;
+    let pyproject_toml = PyProjectToml::parse_with_workspace(&contents, source_tree)?;
+    build_impl(source_tree, wheel_dir, options, &pyproject_toml).await
+}
+
+/// Build a wheel like [`build`], with `key=value` config settings overriding the
+/// `tool.uv.build-backend` settings from `pyproject.toml`, mirroring PEP 517's `config_settings`.
+///
+/// This exposes all build options to frontends without editing files, e.g.,
+/// `compression-level=stored` or `glob-options.case-sensitive=false`. Unknown settings are
+/// rejected.
+pub async fn build_with_config_settings(
+    source_tree: &Path,
+    wheel_dir: &Path,
+    config_settings: &[String],
+) -> Result<BuildResult, Error> {
+    let contents = fs_err::tokio::read_to_string(source_tree.join("pyproject.toml")).await?;
+    let pyproject_toml =
+        PyProjectToml::parse_with_config_settings(&contents, source_tree, config_settings)?;
+    build_impl(
+        source_tree,
+        wheel_dir,
+        BuildOptions::default(),
+        &pyproject_toml,
+    )
+    .await
+}
+
+/// Build a wheel like [`build`], but with the contents of the `pyproject.toml` passed as string
+/// instead of read from the source tree, e.g., for pipelines that template the manifest.
+///
+/// All other files, such as the module and the readme, are still read from `source_tree`.
+pub async fn build_from_pyproject_toml(
+    pyproject_toml: &str,
+    source_tree: &Path,
+    wheel_dir: &Path,
+) -> Result<BuildResult, Error> {
+    let pyproject_toml = PyProjectToml::parse_with_workspace(pyproject_toml, source_tree)?;
+    build_impl(
+        source_tree,
+        wheel_dir,
+        BuildOptions::default(),
+        &pyproject_toml,
+    )
+    .await
+}
+
+async fn build_impl(
+    source_tree: &Path,
+    wheel_dir: &Path,
+    options: BuildOptions<'_>,
+    pyproject_toml: &PyProjectToml,
+) -> Result<BuildResult, Error> {
+    let BuildOptions {
+        metadata_directory,
+        output_policy,
+        temp_dir,
+        metadata_overlay,
+    } = options;
+    pyproject_toml.check_build_system()?;
+
+    let filename = wheel_filename(pyproject_toml);
+
+    let wheel_path = wheel_dir.join(filename.to_string());
+    if wheel_path.exists() {
+        match output_policy {
+            OutputPolicy::Overwrite => {
+                debug!(
+                    "Overwriting existing wheel: `{}`",
+                    wheel_path.user_display()
+                );
+            }
+            OutputPolicy::Error => return Err(Error::OutputExists(wheel_path)),
+            OutputPolicy::Skip => {
+                debug!("Keeping existing wheel: `{}`", wheel_path.user_display());
+                return Ok(BuildResult {
+                    filename,
+                    files: Vec::new(),
+                    total_size: 0,
+                });
+            }
+        }
+    }
+
+    let mut log = BuildLog::new(pyproject_toml.settings().build_log);
+    log.event(BuildEvent::Start {
+        source_tree: source_tree.user_display().to_string(),
+    });
+    log.event(BuildEvent::Resolved {
+        name: filename.name.to_string(),
+        version: filename.version.to_string(),
+        python_tag: filename.python_tag.clone(),
+        abi_tag: filename.abi_tag.clone(),
+        platform_tag: filename.platform_tag.clone(),
+    });
+    let result = write_wheel(
+        source_tree,
+        &wheel_path,
+        metadata_directory,
+        temp_dir.unwrap_or(wheel_dir),
+        pyproject_toml,
+        metadata_overlay,
+        &mut log,
+    )
+    .await;
+    for message in pyproject_toml.warnings() {
+        log.event(BuildEvent::Warning { message });
+    }
+    log.event(BuildEvent::Finish {
+        filename: result.is_ok().then(|| filename.to_string()),
+        error: result.as_ref().err().map(ToString::to_string),
+        uncompressed_size: result.as_ref().ok().map(|size| size.uncompressed),
+        compressed_size: result.as_ref().ok().map(|size| size.compressed),
+    });
+    if let Err(err) = log.write(&wheel_dir.join(format!("{}.build-log.jsonl", filename.stem()))) {
+        // Don't hide the build error behind the error writing the log.
+        if result.is_ok() {
+            return Err(err.into());
+        }
+        warn!("Failed to write the build log: {err}");
+    }
+
+    let size = result?;
+    Ok(BuildResult {
+        filename,
+        files: size.files,
+        total_size: size.uncompressed,
+    })
+}
+
+/// The wheel built by [`build`], with the files it contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildResult {
+    /// The filename of the wheel in the output directory.
+    pub filename: WheelFilename,
+    /// The files in the wheel in archive order, including `RECORD`.
+    ///
+    /// Empty if an existing wheel was kept with [`OutputPolicy::Skip`].
+    pub files: Vec<ArchivedFile>,
+    /// The sum of the sizes of the files, before compression.
+    pub total_size: u64,
+}
+
+/// A file in a built wheel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedFile {
+    /// The path inside the wheel, with `/` as separator.
+    pub path: String,
+    /// The size of the file in bytes, before compression.
+    pub size: u64,
+}
+
+/// The files and the size of a written wheel, in bytes.
+#[derive(Debug, Clone)]
+struct WheelSize {
+    /// The files in the wheel, in archive order.
+    files: Vec<ArchivedFile>,
+    /// The sum of the sizes of all entries before compression.
+    uncompressed: u64,
+    /// The size of the wheel file.
+    compressed: u64,
+}
+
+/// Write the wheel to a temporary file in `temp_dir` and move it to `wheel_path`.
+async fn write_wheel(
+    source_tree: &Path,
+    wheel_path: &Path,
+    metadata_directory: Option<&Path>,
+    temp_dir: &Path,
+    pyproject_toml: &PyProjectToml,
+    metadata_overlay: &[(String, String)],
+    log: &mut BuildLog,
+) -> Result<WheelSize, Error> {
+    // The temporary file is removed when dropped, e.g., on errors.
+    let temp_path = tempfile::Builder::new()
+        .prefix(".tmp-")
+        .suffix(".whl")
+        .tempfile_in(temp_dir)?
+        .into_temp_path();
+    debug!("Writing wheel to: `{}`", temp_path.user_display());
+
+    // TODO(konsti): async-zip doesn't like a buffered writer
+    let wheel_file = fs_err::tokio::File::create(&*temp_path).await?;
+    let mut wheel_writer =
+        AsyncZipWriter::new(wheel_file, pyproject_toml.settings().compression_level)
+            .with_file_modes(file_modes(pyproject_toml)?);
+    log.event(BuildEvent::Step {
+        name: "write-sources",
+    });
+    write_sources(&mut wheel_writer, pyproject_toml, source_tree).await?;
+    write_data_files(&mut wheel_writer, pyproject_toml, source_tree).await?;
+    let source_fingerprint = pyproject_toml
+        .settings()
+        .source_fingerprint
+        .then(|| source_fingerprint(&wheel_writer.record));
+    if let Some(metadata_directory) = metadata_directory {
+        log.event(BuildEvent::Step {
+            name: "copy-dist-info",
+        });
+        let record = copy_dist_info(&mut wheel_writer, pyproject_toml, metadata_directory).await?;
+        if let Some(record) = record {
+            let record_path = format!("{}/RECORD", dist_info_dir(pyproject_toml));
+            check_supplied_record(&wheel_writer.record, &record, &record_path)
+                .map_err(|err| Error::StaleRecord(metadata_directory.join(&record_path), err))?;
+        }
+    } else {
+        log.event(BuildEvent::Step {
+            name: "write-metadata",
+        });
+        write_metadata(
+            &mut wheel_writer,
+            pyproject_toml,
+            source_tree,
+            metadata_overlay,
+        )
+        .await?;
+    }
+    if let Some(source_fingerprint) = source_fingerprint {
+        debug!("Source fingerprint: `{source_fingerprint}`");
+        let build_metadata = serde_json::json!({ "source_fingerprint": source_fingerprint });
+        wheel_writer
+            .write_bytes(
+                &format!("{}/uv_build_metadata.json", dist_info_dir(pyproject_toml)),
+                format!("{build_metadata:#}\n").as_bytes(),
+            )
+            .await?;
+    }
+    for entry in &wheel_writer.record {
+        log.event(BuildEvent::File {
+            path: entry.path.clone(),
+            size: entry.size,
+        });
+    }
+    let record_hash_encoding = pyproject_toml.settings().record_hash_encoding;
+    if record_hash_encoding == RecordHashEncoding::Hex {
+        pyproject_toml.warn(
+            "`tool.uv.build-backend.record-hash-encoding = \"hex\"` is not standard compliant, \
+            installers may reject the wheel"
+                .to_string(),
+        )?;
+    }
+    log.event(BuildEvent::Step {
+        name: "write-record",
+    });
+    wheel_writer
+        .write_record(&dist_info_dir(pyproject_toml), record_hash_encoding)
+        .await?;
+    // The writer sees the bytes of each file, including `RECORD`.
+    let files: Vec<ArchivedFile> = wheel_writer
+        .record
+        .iter()
+        .map(|entry| ArchivedFile {
+            path: entry.path.clone(),
+            size: entry.size as u64,
+        })
+        .collect();
+    let uncompressed = files.iter().map(|file| file.size).sum();
+    wheel_writer.close().await?;
+
+    log.event(BuildEvent::Step { name: "persist" });
+    persist(&temp_path, wheel_path).await?;
+    let compressed = fs_err::tokio::metadata(wheel_path).await?.len();
+    Ok(WheelSize {
+        files,
+        uncompressed,
+        compressed,
+    })
+}
+
+/// Build an editable wheel from the source tree and place it in the output directory.
+///
+/// Instead of the package files, the wheel contains a `.pth` file adding the source roots to
+/// `sys.path`, so changes to the sources take effect without rebuilding. The source roots are
+/// `tool.uv.build-backend.source-roots`, defaulting to the source root of the layout.
+pub async fn build_editable(source_tree: &Path, wheel_dir: &Path) -> Result<WheelFilename, Error> {
+    let contents = fs_err::tokio::read_to_string(source_tree.join("pyproject.toml")).await?;
+    let pyproject_toml = PyProjectToml::parse_with_workspace(&contents, source_tree)?;
+    pyproject_toml.check_build_system()?;
+
+    let source_roots = &pyproject_toml.settings().source_roots;
+    let source_roots = if source_roots.is_empty() {
+        vec![pyproject_toml.settings().source_root(source_tree)]
+    } else {
+        source_roots
+            .iter()
+            .map(|source_root| source_tree.join(source_root))
+            .collect()
+    };
+    let mut pth = String::new();
+    for path in source_roots {
+        let absolute = match path.simple_canonicalize() {
+            Ok(absolute) if absolute.is_dir() => absolute,
+            Ok(_) => return Err(Error::MissingSourceRoot(path)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(Error::MissingSourceRoot(path));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let Some(absolute) = absolute.to_str() else {
+            return Err(Error::NonUtf8SourceRoot(absolute));
+        };
+        debug!("Adding source root to editable: `{absolute}`");
+        pth.push_str(absolute);
+        pth.push('\n');
+    }
+
+    let filename = wheel_filename(&pyproject_toml);
+
+    let temp_path = tempfile::Builder::new()
+        .prefix(".tmp-")
+        .suffix(".whl")
+        .tempfile_in(wheel_dir)?
+        .into_temp_path();
+    let wheel_file = fs_err::tokio::File::create(&*temp_path).await?;
+    // Editables are small and rebuilt often, so they are not compressed.
+    let mut wheel_writer = AsyncZipWriter::new(wheel_file, CompressionLevel::Stored)
+        .with_file_modes(file_modes(&pyproject_toml)?);
+    let pth_file = format!("{}.pth", pyproject_toml.name().as_dist_info_name());
+    wheel_writer.write_bytes(&pth_file, pth.as_bytes()).await?;
+    write_metadata(&mut wheel_writer, &pyproject_toml, source_tree, &[]).await?;
+    wheel_writer
+        .write_record(
+            &dist_info_dir(&pyproject_toml),
+            pyproject_toml.settings().record_hash_encoding,
+        )
+        .await?;
+    wheel_writer.close().await?;
+
+    persist(&temp_path, &wheel_dir.join(filename.to_string())).await?;
+    Ok(filename)
+}
+
+/// A wheel built with [`build_content_addressed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentAddressedWheel {
+    /// The spec-compliant filename of the wheel, e.g., `foo-1.0.0-py3-none-any.whl`.
+    pub filename: WheelFilename,
+    /// The name of the copy that includes a content hash, e.g.,
+    /// `foo-1.0.0-py3-none-any.0123456789abcdef.whl`.
+    ///
+    /// This is not a valid wheel filename, it must be served under the canonical name.
+    pub content_addressed: String,
+}
+
+/// The number of hex digits of the SHA-256 in content-addressed wheel names.
+const CONTENT_HASH_LENGTH: usize = 16;
+
+/// Build a wheel, and additionally copy it to a name that includes a short content hash.
+///
+/// Both files are placed in the output directory and have identical contents. This is useful for
+/// CDNs that cache by filename.
+pub async fn build_content_addressed(
+    source_tree: &Path,
+    wheel_dir: &Path,
+    options: BuildOptions<'_>,
+) -> Result<ContentAddressedWheel, Error> {
+    let filename = build(source_tree, wheel_dir, options).await?.filename;
+
+    // The content-addressed name is derived from the canonical name, so the canonical name must
+    // be valid.
+    let canonical = filename.to_string();
+    let parsed = WheelFilename::from_str(&canonical)
+        .map_err(|err| Error::InvalidWheelFilename(canonical.clone(), err))?;
+    if parsed != filename {
+        return Err(Error::NonCanonicalWheelFilename(canonical));
+    }
+
+    let wheel_path = wheel_dir.join(&canonical);
+    let digest = Sha256::digest(fs_err::tokio::read(&wheel_path).await?);
+    let content_hash = &hex::encode(digest)[..CONTENT_HASH_LENGTH];
+    let content_addressed = format!("{}.{content_hash}.whl", filename.stem());
+    debug!("Copying `{canonical}` to `{content_addressed}`");
+    fs_err::tokio::copy(&wheel_path, wheel_dir.join(&content_addressed)).await?;
+
+    Ok(ContentAddressedWheel {
+        filename,
+        content_addressed,
+    })
+}
+
+/// Build a wheel from a `.tar.gz` source distribution and place it in the output directory.
+///
+/// The source distribution is extracted to a temporary directory, which must contain a single
+/// `{name}-{version}` directory with a `PKG-INFO` that matches the name and version. The wheel is
+/// then built from that directory as with [`build`].
+pub async fn build_from_sdist(sdist_path: &Path, wheel_dir: &Path) -> Result<WheelFilename, Error> {
+    let invalid = |message: String| Error::InvalidSdist(sdist_path.to_path_buf(), message);
+    if !sdist_path
+        .file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .is_some_and(|name| name.ends_with(".tar.gz"))
+    {
+        return Err(invalid("expected a `.tar.gz` file".to_string()));
+    }
+
+    // The temporary directory is removed when dropped, after the build or on errors.
+    let temp_dir = tempfile::tempdir()?;
+    debug!(
+        "Extracting `{}` to: `{}`",
+        sdist_path.user_display(),
+        temp_dir.path().user_display()
+    );
+    let reader = fs_err::tokio::File::open(sdist_path).await?;
+    uv_extract::stream::untar_gz(reader, temp_dir.path())
+        .await
+        .map_err(|err| Error::Extract(sdist_path.to_path_buf(), err))?;
+    let source_tree = uv_extract::strip_component(temp_dir.path())
+        .map_err(|err| Error::Extract(sdist_path.to_path_buf(), err))?;
+
+    let pkg_info = match fs_err::tokio::read(source_tree.join("PKG-INFO")).await {
+        Ok(pkg_info) => pkg_info,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(invalid("missing `PKG-INFO`".to_string()));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let metadata = Metadata23::parse(&pkg_info)
+        .map_err(|err| Error::SdistMetadata(sdist_path.to_path_buf(), err))?;
+    let name = PackageName::from_str(&metadata.name).map_err(|err| {
+        Error::SdistMetadata(sdist_path.to_path_buf(), MetadataError::InvalidName(err))
+    })?;
+    let expected = format!("{}-{}", name.as_dist_info_name(), metadata.version);
+    let top_level = source_tree
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if top_level != expected {
+        return Err(invalid(format!(
+            "expected a top-level `{expected}` directory, found `{top_level}`"
+        )));
+    }
+
+    let result = build(&source_tree, wheel_dir, BuildOptions::default()).await?;
+    Ok(result.filename)
+}
+
+/// Build a `.tar.gz` source distribution from the source tree and place it in the output
+/// directory.
+///
+/// The source distribution contains a single `{name}-{version}` directory with the
+/// `pyproject.toml`, a `PKG-INFO` with the core metadata, the `src` directory, the readme, the
+/// license files, the data files, the constraints file and the entry points file, so a wheel can
+/// be built from it with [`build_from_sdist`].
+pub async fn build_sdist(
+    source_tree: &Path,
+    sdist_dir: &Path,
+) -> Result<SourceDistFilename, Error> {
+    let contents = fs_err::tokio::read_to_string(source_tree.join("pyproject.toml")).await?;
+    let pyproject_toml = PyProjectToml::parse_with_workspace(&contents, source_tree)?;
+    pyproject_toml.check_build_system()?;
+    let metadata = pyproject_toml.to_sdist_metadata(source_tree)?;
+    let sdist_pyproject_toml = pyproject_toml.sdist_pyproject_toml(&contents, source_tree)?;
+
+    let filename = SourceDistFilename {
+        name: pyproject_toml.name().clone(),
+        version: pyproject_toml.version().clone(),
+        extension: SourceDistExtension::TarGz,
+    };
+    let top_level = format!(
+        "{}-{}",
+        pyproject_toml.name().as_dist_info_name(),
+        pyproject_toml.version()
+    );
+
+    // The temporary file is removed when dropped, e.g., on errors.
+    let temp_path = tempfile::Builder::new()
+        .prefix(".tmp-")
+        .suffix(".tar.gz")
+        .tempfile_in(sdist_dir)?
+        .into_temp_path();
+    debug!(
+        "Writing source distribution to: `{}`",
+        temp_path.user_display()
+    );
+    let sdist_file = fs_err::tokio::File::create(&*temp_path).await?;
+    let mut writer = AsyncTarGzWriter::new(sdist_file, file_modes(&pyproject_toml)?);
+
+    writer
+        .write_bytes(
+            &format!("{top_level}/pyproject.toml"),
+            sdist_pyproject_toml.as_bytes(),
+        )
+        .await?;
+    writer
+        .write_bytes(
+            &format!("{top_level}/PKG-INFO"),
+            pyproject_toml.format_metadata(&metadata).as_bytes(),
+        )
+        .await?;
+
+    let mut files: BTreeSet<PathBuf> = BTreeSet::new();
+    // In the flat layout, only the module is packaged, not everything next to `pyproject.toml`.
+    let src = pyproject_toml.settings().source_root(source_tree);
+    let src = if src == source_tree {
+        src.join(module_name(&pyproject_toml)?)
+    } else {
+        src
+    };
+    for entry in WalkDir::new(&src)
+        .sort_by_file_name()
+        .follow_links(pyproject_toml.settings().symlinks == Symlinks::Follow)
+        .into_iter()
+        .filter_entry(|entry| !is_vcs_dir(entry) && !is_pycache_dir(entry))
+    {
+        let entry = entry.map_err(|err| Error::WalkDir {
+            root: src.clone(),
+            err,
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(source_tree)
+            .expect("walkdir starts with root");
+        if !is_bytecode_file(relative) {
+            files.insert(relative.to_path_buf());
+        }
+    }
+    // The files outside `src` that building the wheel reads.
+    files.extend(pyproject_toml.readme_file().map(Path::to_path_buf));
+    files.extend(pyproject_toml.license_file().map(Path::to_path_buf));
+    files.extend(metadata.license_files.iter().map(PathBuf::from));
+    files.extend(
+        pyproject_toml
+            .settings()
+            .data_files
+            .keys()
+            .map(PathBuf::from),
+    );
+    let match_options = pyproject_toml.settings().glob_options.match_options();
+    for glob in pyproject_toml.settings().data.values().flatten() {
+        let matches = expand_data_glob(source_tree, glob, match_options)?;
+        if matches.is_empty() {
+            warn_data_glob_no_match(&pyproject_toml, glob)?;
+        }
+        for (file, _) in matches {
+            files.insert(PathBuf::from(file));
+        }
+    }
+    files.extend(pyproject_toml.settings().constraints.clone());
+    files.extend(pyproject_toml.settings().entry_points_file.clone());
+    files.extend(pyproject_toml.settings().manifest.clone());
+
+    for relative in files {
+        if relative.to_str().is_none() {
+            return Err(Error::NonUtf8Path(relative));
+        }
+        debug!(
+            "Adding to source distribution: `{}`",
+            relative.user_display()
+        );
+        let bytes = fs_err::tokio::read(source_tree.join(&relative)).await?;
+        writer
+            .write_bytes(
+                &format!("{top_level}/{}", relative.portable_display()),
+                &bytes,
+            )
+            .await?;
+    }
+    writer.close().await?;
+
+    persist(&temp_path, &sdist_dir.join(filename.to_string())).await?;
+    Ok(filename)
+}
+
+/// Build wheels for multiple source trees concurrently, placing them in the output directory.
+///
+/// At most `concurrency` builds run at the same time, to avoid exhausting file descriptors. The
+/// results are in the order of the source trees; a failing build doesn't stop the others.
+///
+/// Each source tree is one distribution, building several distributions from a single tree is
+/// not supported. The `options` apply to every build.
+pub async fn build_many(
+    source_trees: &[&Path],
+    wheel_dir: &Path,
+    options: BuildOptions<'_>,
+    concurrency: usize,
+) -> Vec<Result<BuildResult, Error>> {
+    futures::stream::iter(source_trees)
+        .map(|source_tree| build(source_tree, wheel_dir, options))
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// How a relative wheel directory passed to [`wheel_directory`] is resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RelativeWheelDirectory {
+    /// Relative to the current directory of the process, as any other relative path.
+    #[default]
+    CurrentDirectory,
+    /// Relative to the project directory in `UV_BUILD_PROJECT_DIR`, so the wheel ends up in the
+    /// same place independent of where the build is invoked from.
+    ///
+    /// Without `UV_BUILD_PROJECT_DIR`, this is the same as
+    /// [`RelativeWheelDirectory::CurrentDirectory`].
+    ProjectDirectory,
+}
+
+/// Build wheels for multiple source trees, with each build holding a permit of `semaphore`.
+///
+/// Unlike [`build_many`], the concurrency limit is shared: Embedders such as build servers can
+/// pass the same semaphore to all calls to bound the number of builds across calls. The builds
+/// run on the caller's runtime.
+pub async fn build_many_limited(
+    source_trees: &[&Path],
+    wheel_dir: &Path,
+    options: BuildOptions<'_>,
+    semaphore: &Semaphore,
+) -> Vec<Result<BuildResult, Error>> {
+    futures::stream::iter(source_trees)
+        .map(|source_tree| async move {
+            let _permit = semaphore
+                .acquire()
+                .await
+                .map_err(|_| Error::SemaphoreClosed)?;
+            build(source_tree, wheel_dir, options).await
+        })
+        .buffered(source_trees.len().max(1))
+        .collect()
+        .await
+}
+
+/// Build a wheel on an existing runtime instead of creating one, blocking until it's done.
+///
+/// This is for synchronous callers that share a runtime between builds. It must not be called
+/// from within an async task, see [`Handle::block_on`].
+pub fn build_on(
+    handle: &Handle,
+    source_tree: &Path,
+    wheel_dir: &Path,
+    options: BuildOptions<'_>,
+) -> Result<BuildResult, Error> {
+    handle.block_on(build(source_tree, wheel_dir, options))
+}
+
+/// Determine the directory to place the wheel in.
+///
+/// Prefer, in order:
+///
+/// 1. The `wheel_directory` passed explicitly by the caller. If it is relative, it is resolved
+///    according to `relative_to`, by default against the current directory.
+/// 2. The directory in `UV_BUILD_PROJECT_DIR`, for containerized builds where the project is
+///    mounted at a path that is only known at runtime
+///
+/// Returns `None` if neither is set.
+pub fn wheel_directory(
+    wheel_directory: Option<&Path>,
+    relative_to: RelativeWheelDirectory,
+) -> Option<PathBuf> {
+    resolve_wheel_directory(
+        wheel_directory,
+        std::env::var_os("UV_BUILD_PROJECT_DIR").map(PathBuf::from),
+        relative_to,
+    )
+}
+
+fn resolve_wheel_directory(
+    wheel_directory: Option<&Path>,
+    from_env: Option<PathBuf>,
+    relative_to: RelativeWheelDirectory,
+) -> Option<PathBuf> {
+    let from_env = from_env.filter(|path| !path.as_os_str().is_empty());
+    if let Some(wheel_directory) = wheel_directory {
+        if let (RelativeWheelDirectory::ProjectDirectory, Some(project_dir)) =
+            (relative_to, &from_env)
+        {
+            if wheel_directory.is_relative() {
+                let resolved = project_dir.join(wheel_directory);
+                debug!(
+                    "Resolving wheel directory relative to `UV_BUILD_PROJECT_DIR`: `{}`",
+                    resolved.user_display()
+                );
+                return Some(resolved);
+            }
+        }
+        return Some(wheel_directory.to_path_buf());
+    }
+    let from_env = from_env?;
+    debug!(
+        "Using wheel directory from `UV_BUILD_PROJECT_DIR`: `{}`",
+        from_env.user_display()
+    );
+    Some(from_env)
+}
+
+/// A hash over the paths and contents of the files, independent of the order they were written
+/// in, as `sha256:<hex>`.
+fn source_fingerprint(files: &[RecordEntry]) -> String {
+    let mut files: Vec<&RecordEntry> = files.iter().collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let mut hasher = Sha256::new();
+    for entry in files {
+        // The path can't contain a NUL byte, so the boundaries are unambiguous.
+        hasher.update(entry.path.as_bytes());
+        hasher.update([0]);
+        hasher.update(&entry.hash);
+    }
+    format!("sha256:{}", hex::encode(hasher.finalize()))
+}
+
+/// The hash of a file for `RECORD`, e.g., `sha256=<urlsafe base64 without padding>`.
+fn hash_digest(bytes: &[u8]) -> String {
+    RecordHashEncoding::Base64.format(&Sha256::digest(bytes))
+}
+
+/// Move the finished file into place, copying it if it is on a different filesystem.
+async fn persist(temp_path: &Path, path: &Path) -> Result<(), Error> {
+    if let Err(err) = fs_err::tokio::rename(temp_path, path).await {
+        debug!("Failed to rename, copying instead: {err}");
+        fs_err::tokio::copy(temp_path, path).await?;
+    }
+    Ok(())
+}
+
+/// Write the dist-info directory to the output directory without building the wheel.
+///
+/// The `metadata_overlay` is applied to `METADATA` as in [`BuildOptions::metadata_overlay`].
+///
+/// Returns the name of the dist-info directory.
+pub async fn metadata(
+    source_tree: &Path,
+    metadata_directory: &Path,
+    metadata_overlay: &[(String, String)],
+) -> Result<String, Error> {
+    let contents = fs_err::tokio::read_to_string(source_tree.join("pyproject.toml")).await?;
+    let pyproject_toml = PyProjectToml::parse_with_workspace(&contents, source_tree)?;
+    pyproject_toml.check_build_system()?;
+
+    let mut wheel_writer = AsyncFsWriter {
+        root: metadata_directory.to_path_buf(),
+    };
+    write_metadata(
+        &mut wheel_writer,
+        &pyproject_toml,
+        source_tree,
+        metadata_overlay,
+    )
+    .await?;
+    wheel_writer.close().await?;
+
+    Ok(dist_info_dir(&pyproject_toml))
+}
+
+/// Read the project and return its core metadata, i.e., the contents of the `METADATA` file.
+///
+/// Unlike [`metadata`], this is synchronous and writes nothing, for callers without an async
+/// runtime that only need the metadata.
+pub fn metadata_string_blocking(source_tree: &Path) -> Result<String, Error> {
+    let contents = fs_err::read_to_string(source_tree.join("pyproject.toml"))?;
+    let pyproject_toml = PyProjectToml::parse_with_workspace(&contents, source_tree)?;
+    pyproject_toml.check_build_system()?;
+
+    let metadata = pyproject_toml.to_metadata(source_tree)?;
+    Ok(pyproject_toml.format_metadata(&metadata))
+}
+
+/// Return the core metadata in a canonical form for hashing, e.g., to fingerprint a project.
+///
+/// The fields are always written in the same order, independent of the order in
+/// `pyproject.toml`, and the whitespace is normalized: line endings are `\n`, trailing whitespace
+/// is removed, and the output ends with exactly one newline.
+pub fn canonical_metadata(source_tree: &Path) -> Result<String, Error> {
+    let metadata = metadata_string_blocking(source_tree)?;
+    let mut canonical = metadata
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    canonical.truncate(canonical.trim_end().len());
+    canonical.push('\n');
+    Ok(canonical)
+}
+
+/// The entry points of a wheel, mapping each group (e.g., `console_scripts`) to its entry point
+/// names and their targets (e.g., `foo = foo.cli:main`).
+pub type EntryPoints = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Read and parse the `entry_points.txt` from the dist-info directory of a wheel.
+///
+/// A wheel without `entry_points.txt` has no entry points, so an empty map is returned.
+///
+/// <https://packaging.python.org/en/latest/specifications/entry-points/>
+pub fn read_wheel_entry_points(wheel_path: &Path) -> Result<EntryPoints, Error> {
+    let mut archive = zip::ZipArchive::new(fs_err::File::open(wheel_path)?)
+        .map_err(|err| Error::ReadZip(wheel_path.to_path_buf(), err))?;
+    let entry_points_txt = archive.file_names().find(|name| {
+        name.split_once('/').is_some_and(|(directory, file)| {
+            directory.ends_with(".dist-info") && file == "entry_points.txt"
+        })
+    });
+    let Some(entry_points_txt) = entry_points_txt.map(ToString::to_string) else {
+        debug!("No `entry_points.txt` in `{}`", wheel_path.user_display());
+        return Ok(EntryPoints::new());
+    };
+    let mut contents = String::new();
+    archive
+        .by_name(&entry_points_txt)
+        .map_err(|err| Error::ReadZip(wheel_path.to_path_buf(), err))?
+        .read_to_string(&mut contents)?;
+    parse_entry_points(&contents)
+}
+
+/// Parse the ini-like `entry_points.txt` format.
+fn parse_entry_points(contents: &str) -> Result<EntryPoints, Error> {
+    let mut entry_points = EntryPoints::new();
+    let mut group = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            group = Some(entry_points.entry(name.trim().to_string()).or_default());
+            continue;
+        }
+        let (Some(group), Some((name, target))) = (group.as_mut(), line.split_once('=')) else {
+            return Err(Error::EntryPoints(line.to_string()));
+        };
+        group.insert(name.trim().to_string(), target.trim().to_string());
+    }
+    Ok(entry_points)
+}
+
+/// The filename of the wheel, which is always pure Python for now.
+fn wheel_filename(pyproject_toml: &PyProjectToml) -> WheelFilename {
+    WheelFilename {
+        name: pyproject_toml.name().clone(),
+        version: pyproject_toml.version().clone(),
+        build_tag: None,
+        python_tag: vec!["py3".to_string()],
+        abi_tag: vec!["none".to_string()],
+        platform_tag: vec!["any".to_string()],
+    }
+    .with_canonical_tags()
+}
+
+/// The `WHEEL` file of the dist-info directory.
+///
+/// <https://packaging.python.org/en/latest/specifications/binary-distribution-format/#the-dist-info-directory>
+fn wheel_info(pyproject_toml: &PyProjectToml, filename: &WheelFilename) -> Result<String, Error> {
+    let platform_independent = filename.platform_tag.iter().all(|tag| tag == "any");
+    let root_is_purelib = match pyproject_toml.settings().root_is_purelib {
+        Some(root_is_purelib) => {
+            if root_is_purelib && !platform_independent {
+                pyproject_toml.warn(format!(
+                    "`tool.uv.build-backend.root-is-purelib` is set, but the wheel has platform \
+                    tags: `{filename}`"
+                ))?;
+            }
+            root_is_purelib
+        }
+        None => platform_independent,
+    };
+
+    let mut wheel_info = String::new();
+    let _ = writeln!(wheel_info, "Wheel-Version: 1.0");
+    let _ = writeln!(wheel_info, "Generator: uv {}", uv_version::version());
+    let _ = writeln!(wheel_info, "Root-Is-Purelib: {root_is_purelib}");
+    for python_tag in &filename.python_tag {
+        for abi_tag in &filename.abi_tag {
+            for platform_tag in &filename.platform_tag {
+                let _ = writeln!(wheel_info, "Tag: {python_tag}-{abi_tag}-{platform_tag}");
+            }
+        }
+    }
+    Ok(wheel_info)
+}
+
+/// The name of the `.dist-info` directory, e.g., `foo-1.2.3.dist-info`.
+fn dist_info_dir(pyproject_toml: &PyProjectToml) -> String {
+    format!(
+        "{}-{}.dist-info",
+        pyproject_toml.name().as_dist_info_name(),
+        pyproject_toml.version()
+    )
+}
+
+/// Add `METADATA`, the license files, `entry_points.txt` and `WHEEL` to the dist-info
+/// directory.
+async fn write_metadata(
+    writer: &mut impl AsyncDirectoryWrite,
+    pyproject_toml: &PyProjectToml,
+    root: &Path,
+    metadata_overlay: &[(String, String)],
+) -> Result<(), Error> {
+    let dist_info_dir = dist_info_dir(pyproject_toml);
+
+    let mut metadata = pyproject_toml.to_metadata(root)?;
+    apply_overlay(&mut metadata, metadata_overlay)?;
+    writer
+        .write_bytes(
+            &format!("{dist_info_dir}/METADATA"),
+            pyproject_toml.format_metadata(&metadata).as_bytes(),
+        )
+        .await?;
+
+    // PEP 639 license files, which have already been validated to be inside the project.
+    let license_dir = pyproject_toml.settings().license_dir;
+    if license_dir != LicenseDir::Licenses && !metadata.license_files.is_empty() {
+        pyproject_toml.warn(
+            "`tool.uv.build-backend.license-dir` places license files in the dist-info root, \
+            which is not standard compliant"
+                .to_string(),
+        )?;
+    }
+    for license_file in &metadata.license_files {
+        debug!("Adding license file to wheel: `{license_file}`");
+        let bytes = fs_err::tokio::read(root.join(license_file)).await?;
+        if matches!(license_dir, LicenseDir::Licenses | LicenseDir::Both) {
+            writer
+                .write_bytes(&format!("{dist_info_dir}/licenses/{license_file}"), &bytes)
+                .await?;
+        }
+        if matches!(license_dir, LicenseDir::Root | LicenseDir::Both) {
+            writer
+                .write_bytes(&format!("{dist_info_dir}/{license_file}"), &bytes)
+                .await?;
+        }
+    }
+
+    let from_file = if let Some(entry_points_file) = &pyproject_toml.settings().entry_points_file {
+        let path = root.join(entry_points_file);
+        debug!("Reading entry points from: `{}`", path.user_display());
+        parse_entry_points(&fs_err::tokio::read_to_string(&path).await?)
+            .map_err(|err| Error::EntryPointsFile(path, Box::new(err)))?
+    } else {
+        EntryPoints::new()
+    };
+    if let Some(entrypoint) = pyproject_toml.to_entry_points(root, &from_file)? {
+        writer
+            .write_bytes(
+                &format!("{dist_info_dir}/entry_points.txt"),
+                entrypoint.as_bytes(),
+            )
+            .await?;
+    }
+
+    let wheel_info = wheel_info(pyproject_toml, &wheel_filename(pyproject_toml))?;
+    writer
+        .write_bytes(&format!("{dist_info_dir}/WHEEL"), wheel_info.as_bytes())
+        .await?;
+
+    Ok(())
+}
+
+/// Add the files of a previously prepared `.dist-info` directory to the wheel.
+///
+/// The directory name must match the project, otherwise the metadata belongs to a different
+/// build.
+///
+/// `RECORD` is not copied since it is regenerated, its contents are returned instead if it exists.
+async fn copy_dist_info(
+    writer: &mut impl AsyncDirectoryWrite,
+    pyproject_toml: &PyProjectToml,
+    metadata_directory: &Path,
+) -> Result<Option<Vec<u8>>, Error> {
+    let dist_info_dir = dist_info_dir(pyproject_toml);
+    let dist_info_root = metadata_directory.join(&dist_info_dir);
+    if !dist_info_root.is_dir() {
+        return Err(Error::MissingDistInfo {
+            metadata_directory: metadata_directory.to_path_buf(),
+            expected: dist_info_dir,
+        });
+    }
+
+    let mut record = None;
+    for entry in WalkDir::new(&dist_info_root).sort_by_file_name() {
+        let entry = entry.map_err(|err| Error::WalkDir {
+            root: dist_info_root.clone(),
+            err,
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(metadata_directory)
+            .expect("walkdir starts with root");
+        let bytes = fs_err::tokio::read(entry.path()).await?;
+        if relative == Path::new(&dist_info_dir).join("RECORD") {
+            record = Some(bytes);
+            continue;
+        }
+        debug!("Adding metadata to wheel: `{}`", relative.user_display());
+        writer
+            .write_bytes(&relative.portable_display().to_string(), &bytes)
+            .await?;
+    }
+
+    Ok(record)
+}
+
+/// Check that a `RECORD` from the metadata directory lists exactly the packaged files, with
+/// matching hashes and sizes.
+///
+/// A mismatch means that the metadata was prepared for different sources, e.g., because the
+/// sources changed since.
+fn check_supplied_record(
+    packaged: &[RecordEntry],
+    record: &[u8],
+    record_path: &str,
+) -> Result<(), InstalledLayoutError> {
+    let mut listed = read_record(record, record_path)?;
+    listed.remove(record_path);
+    for entry in packaged {
+        let Some(listed_entry) = listed.remove(&entry.path) else {
+            return Err(InstalledLayoutError::NotInRecord(entry.path.clone()));
+        };
+        if let Some(expected) = listed_entry.hash {
+            let actual = RecordHashEncoding::Base64.format(&entry.hash);
+            if expected != actual {
+                return Err(InstalledLayoutError::HashMismatch {
+                    path: entry.path.clone(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+        if let Some(expected) = listed_entry.size {
+            let actual = entry.size as u64;
+            if expected != actual {
+                return Err(InstalledLayoutError::SizeMismatch {
+                    path: entry.path.clone(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+    if let Some(missing) = listed.into_keys().next() {
+        return Err(InstalledLayoutError::MissingRecordFile(missing));
+    }
+    Ok(())
+}
+
+/// The default for `tool.uv.build-backend.max-walk-depth`.
+const DEFAULT_MAX_WALK_DEPTH: usize = 64;
+
+/// Add the files of the Python module to the wheel.
+///
+/// The module is expected at `<source root>/<module_name>`, where the source root is `src` in the
+/// src layout and the project root in the flat layout.
+async fn write_sources(
+    writer: &mut impl AsyncDirectoryWrite,
+    pyproject_toml: &PyProjectToml,
+    root: &Path,
+) -> Result<(), Error> {
+    let src = pyproject_toml.settings().source_root(root);
+    let stubs = pyproject_toml.settings().stubs;
+    let module_root = src.join(module_name(pyproject_toml)?);
+    if !module_root.is_dir() {
+        return Err(Error::MissingModule(module_root));
+    }
+    // The namespace directories are shared with other distributions, an `__init__.py` would make
+    // them a regular package that shadows the other parts of the namespace.
+    if pyproject_toml.settings().namespace.is_some() {
+        for parent in module_root.ancestors().skip(1) {
+            if parent == src {
+                break;
+            }
+            let init_py = parent.join("__init__.py");
+            if init_py.is_file() {
+                pyproject_toml.warn(format!(
+                    "The namespace package contains an `__init__.py`, which is not packaged: `{}`",
+                    init_py
+                        .strip_prefix(root)
+                        .unwrap_or(init_py.as_path())
+                        .user_display()
+                ))?;
+            }
+        }
+    }
+    let include_tests = pyproject_toml.settings().include_tests;
+    let check_syntax = pyproject_toml.settings().check_syntax;
+    let include = parse_source_globs("include", &pyproject_toml.settings().include)?;
+    let exclude = parse_source_globs("exclude", &pyproject_toml.settings().exclude)?;
+    // An include glob that matches nothing is likely a typo.
+    let mut include_matched = vec![false; include.len()];
+    let match_options = pyproject_toml.settings().glob_options.match_options();
+    let manifest = if let Some(manifest) = &pyproject_toml.settings().manifest {
+        let path = root.join(manifest);
+        debug!("Reading manifest from: `{}`", path.user_display());
+        Manifest::parse(&fs_err::tokio::read_to_string(&path).await?)
+            .map_err(|err| Error::Manifest(path, err))?
+    } else {
+        Manifest::default()
+    };
+    let symlinks = pyproject_toml.settings().symlinks;
+    let max_file_size = pyproject_toml.settings().max_file_size;
+    let max_depth = pyproject_toml
+        .settings()
+        .max_walk_depth
+        .unwrap_or(DEFAULT_MAX_WALK_DEPTH);
+    // Compiled extensions only work on the platform they were built for.
+    let platform_independent = wheel_filename(pyproject_toml)
+        .platform_tag
+        .iter()
+        .all(|tag| tag == "any");
+    let mut python_files = Vec::new();
+    let mut has_stubs = false;
+
+    // When following symlinks, the paths are still the logical paths below the module root, so
+    // a symlinked directory is packaged under its import path, not under its target. Symlink
+    // cycles are detected by walkdir, which tracks the ancestors of each directory; the depth
+    // limit additionally bounds the walk for deep chains of symlinks that never cycle.
+    for entry in WalkDir::new(&module_root)
+        .sort_by_file_name()
+        .follow_links(symlinks == Symlinks::Follow)
+        .into_iter()
+        // VCS directories are never packaged, they would leak the repository history. Bytecode
+        // caches are regenerated on install.
+        .filter_entry(|entry| !is_vcs_dir(entry) && !is_pycache_dir(entry))
+    {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) if err.loop_ancestor().is_some() => {
+                let path = err.path().unwrap_or(&module_root);
+                let relative = path.strip_prefix(&src).unwrap_or(path);
+                return Err(Error::SymlinkCycle(relative.to_path_buf()));
+            }
+            Err(err) => {
+                return Err(Error::WalkDir {
+                    root: module_root.clone(),
+                    err,
+                })
+            }
+        };
+        if entry.depth() > max_depth {
+            let relative = entry.path().strip_prefix(&src).unwrap_or(entry.path());
+            return Err(Error::MaxWalkDepth {
+                path: relative.to_path_buf(),
+                max_depth,
+            });
+        }
+        if entry.path_is_symlink() && symlinks != Symlinks::Follow {
+            let relative = entry.path().strip_prefix(&src).unwrap_or(entry.path());
+            if symlinks == Symlinks::Error {
+                return Err(Error::Symlink(relative.to_path_buf()));
+            }
+            debug!("Skipping symlink: `{}`", relative.user_display());
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(&src)
+            .expect("walkdir starts with root");
+        if is_bytecode_file(relative) {
+            debug!("Excluding bytecode: `{}`", relative.user_display());
+            continue;
+        }
+        if relative.to_str().is_none() {
+            match pyproject_toml.settings().non_utf8_filenames {
+                NonUtf8Filenames::Error => return Err(Error::NonUtf8Path(relative.to_path_buf())),
+                NonUtf8Filenames::Skip => {
+                    pyproject_toml.warn(format!(
+                        "Skipping file with a non-UTF-8 name: `{}`",
+                        relative.user_display()
+                    ))?;
+                    continue;
+                }
+            }
+        }
+        if !include_tests && is_test_file(relative) {
+            debug!("Excluding test file: `{}`", relative.user_display());
+            continue;
+        }
+        let mut included = include.is_empty();
+        for (pattern, matched) in include.iter().zip(&mut include_matched) {
+            if pattern.matches_path_with(relative, match_options) {
+                *matched = true;
+                included = true;
+            }
+        }
+        let excluded = exclude
+            .iter()
+            .any(|pattern| pattern.matches_path_with(relative, match_options));
+        // The manifest patterns are relative to the project root, not to the source root.
+        let project_relative = entry.path().strip_prefix(root).unwrap_or(relative);
+        if !manifest.is_included(project_relative, included && !excluded, match_options) {
+            debug!("Not included: `{}`", relative.user_display());
+            continue;
+        }
+        if stubs {
+            if relative
+                .extension()
+                .is_some_and(|extension| extension == "pyi")
+            {
+                has_stubs = true;
+            } else if relative.file_name().is_some_and(|name| name != "py.typed") {
+                debug!("Excluding non-stub file: `{}`", relative.user_display());
+                continue;
+            }
+        }
+
+        // Skipped files are neither checked nor packaged.
+        let mut file = fs_err::tokio::File::open(entry.path()).await?;
+        let size = file.metadata().await?.len();
+        if let Some(max_file_size) = max_file_size {
+            if size > max_file_size {
+                pyproject_toml.warn(format!(
+                    "Skipping `{}` ({size} bytes), it is larger than `tool.uv.build-backend.max-file-size = {max_file_size}`",
+                    relative.user_display()
+                ))?;
+                continue;
+            }
+        }
+
+        if platform_independent && is_compiled_extension(relative) {
+            return Err(Error::CompiledExtension(relative.to_path_buf()));
+        }
+
+        if check_syntax
+            && relative
+                .extension()
+                .is_some_and(|extension| extension == "py")
+        {
+            python_files.push(entry.path().to_path_buf());
+        }
+
+        debug!("Adding to wheel: `{}`", relative.user_display());
+        writer
+            .write_reader(&relative.portable_display().to_string(), &mut file, size)
+            .await?;
+    }
+
+    for (pattern, matched) in include.iter().zip(include_matched) {
+        if !matched {
+            pyproject_toml.warn(format!(
+                "The `tool.uv.build-backend.include` glob doesn't match any files: `{pattern}`"
+            ))?;
+        }
+    }
+    if stubs && !has_stubs {
+        return Err(Error::MissingStubs(module_root));
+    }
+    if check_syntax {
+        check_python_syntax(&python_files, root).await?;
+    }
+
+    Ok(())
+}
+
+/// The validated `tool.uv.build-backend.default-file-mode` and `default-executable-mode`.
+///
+/// The owner must be able to read all files and to execute the executables, other bits, e.g.,
+/// setuid, are rejected.
+fn file_modes(pyproject_toml: &PyProjectToml) -> Result<FileModes, Error> {
+    let settings = pyproject_toml.settings();
+    let defaults = FileModes::default();
+    let file = settings.default_file_mode.unwrap_or(defaults.file);
+    if file > 0o777 || file & 0o400 == 0 {
+        return Err(Error::InvalidFileMode {
+            setting: "default-file-mode",
+            access: "read",
+            mode: file,
+        });
+    }
+    let executable = settings
+        .default_executable_mode
+        .unwrap_or(defaults.executable);
+    if executable > 0o777 || executable & 0o500 != 0o500 {
+        return Err(Error::InvalidFileMode {
+            setting: "default-executable-mode",
+            access: "read and execute",
+            mode: executable,
+        });
+    }
+    Ok(FileModes { file, executable })
+}
+
+/// The directory of the module relative to the source root, which is the stubs directory for
+/// stub-only distributions and, e.g., `acme/plugin` for a module in a namespace package.
+fn module_name(pyproject_toml: &PyProjectToml) -> Result<PathBuf, Error> {
+    if let Some(namespace) = &pyproject_toml.settings().namespace {
+        if !namespace.split('.').all(is_identifier) {
+            return Err(Error::InvalidNamespace(namespace.clone()));
+        }
+        Ok(namespace.split('.').collect())
+    } else if pyproject_toml.settings().stubs {
+        Ok(PathBuf::from(stubs_module_name(pyproject_toml)?))
+    } else {
+        Ok(PathBuf::from(
+            pyproject_toml.name().as_dist_info_name().to_string(),
+        ))
+    }
+}
+
+/// Parse the `tool.uv.build-backend.include` or `exclude` globs.
+fn parse_source_globs(
+    setting: &'static str,
+    globs: &[String],
+) -> Result<Vec<glob::Pattern>, Error> {
+    globs
+        .iter()
+        .map(|glob| {
+            glob::Pattern::new(glob).map_err(|err| Error::SourceGlob {
+                setting,
+                glob: glob.clone(),
+                err,
+            })
+        })
+        .collect()
+}
+
+/// The directory of a PEP 561 stub-only package, e.g., `foo-stubs` for the `foo-stubs`
+/// distribution, which contains the stubs for the `foo` module.
+///
+/// <https://peps.python.org/pep-0561/#stub-only-packages>
+fn stubs_module_name(pyproject_toml: &PyProjectToml) -> Result<String, Error> {
+    let name = pyproject_toml.name().as_ref();
+    let module = match name.strip_suffix("-stubs") {
+        Some(module) => module,
+        None => {
+            pyproject_toml.warn(format!(
+                "Stub-only distributions should be named `<module>-stubs`, not `{name}`"
+            ))?;
+            name
+        }
+    };
+    Ok(format!("{}-stubs", module.replace('-', "_")))
+}
+
+/// The categories of the `.data` directory, each installed to the directory of the same name in
+/// the install scheme.
+///
+/// <https://packaging.python.org/en/latest/specifications/binary-distribution-format/#the-data-directory>
+const DATA_CATEGORIES: &[&str] = &["purelib", "platlib", "headers", "scripts", "data"];
+
+/// Add the files from `tool.uv.build-backend.data-files` and `tool.uv.build-backend.data` to
+/// the `.data` directory.
+///
+/// Scripts are marked as executable, so they stay runnable after installation.
+async fn write_data_files(
+    writer: &mut impl AsyncDirectoryWrite,
+    pyproject_toml: &PyProjectToml,
+    root: &Path,
+) -> Result<(), Error> {
+    let data_dir = format!(
+        "{}-{}.data",
+        pyproject_toml.name().as_dist_info_name(),
+        pyproject_toml.version()
+    );
+    let match_options = pyproject_toml.settings().glob_options.match_options();
+    let mut files = Vec::new();
+    for (file, destination) in &pyproject_toml.settings().data_files {
+        files.push((
+            file.clone(),
+            resolve_data_destination(&data_dir, file, destination)?,
+        ));
+    }
+    for (category, globs) in &pyproject_toml.settings().data {
+        if !DATA_CATEGORIES.contains(&category.as_str()) {
+            return Err(Error::InvalidDataCategory(category.clone()));
+        }
+        for glob in globs {
+            let matches = expand_data_glob(root, glob, match_options)?;
+            if matches.is_empty() {
+                warn_data_glob_no_match(pyproject_toml, glob)?;
+            }
+            for (file, path) in matches {
+                files.push((file, format!("{data_dir}/{category}/{path}")));
+            }
+        }
+    }
+
+    for (file, path) in files {
+        debug!("Adding data file to wheel: `{file}` as `{path}`");
+        let bytes = fs_err::tokio::read(root.join(&file)).await?;
+        if path.starts_with(&format!("{data_dir}/scripts/")) {
+            writer.write_executable(&path, &bytes).await?;
+        } else {
+            writer.write_bytes(&path, &bytes).await?;
+        }
+    }
+    Ok(())
+}
+
+/// A data glob that matches nothing is likely a typo.
+fn warn_data_glob_no_match(pyproject_toml: &PyProjectToml, glob: &str) -> Result<(), Error> {
+    pyproject_toml.warn(format!(
+        "The `tool.uv.build-backend.data` glob doesn't match any files: `{glob}`"
+    ))?;
+    Ok(())
+}
+
+/// The files matching a `tool.uv.build-backend.data` glob, as pairs of the path relative to the
+/// project root and the path relative to the directory before the first wildcard.
+///
+/// Parent directories and absolute paths are rejected up front, and each match must still
+/// resolve inside the project root, so a symlink can't pull in files from elsewhere.
+fn expand_data_glob(
+    root: &Path,
+    glob: &str,
+    match_options: glob::MatchOptions,
+) -> Result<Vec<(String, String)>, Error> {
+    let components: Vec<&str> = glob.split('/').collect();
+    if glob.starts_with('/')
+        || glob.contains('\\')
+        || components.iter().any(|component| *component == "..")
+    {
+        return Err(Error::DataGlob(glob.to_string()));
+    }
+    // The literal directories before the first wildcard, never including the file name.
+    let base_len = components
+        .iter()
+        .take(components.len() - 1)
+        .take_while(|component| !component.contains(['*', '?', '[']))
+        .count();
+    let base = components[..base_len].join("/");
+
+    let absolute_glob = PathBuf::from(glob::Pattern::escape(
+        root.simplified().to_string_lossy().as_ref(),
+    ))
+    .join(glob)
+    .to_string_lossy()
+    .to_string();
+    let canonical_root = root.simple_canonicalize()?;
+    let mut files = Vec::new();
+    for file in glob::glob_with(&absolute_glob, match_options).map_err(|err| Error::SourceGlob {
+        setting: "data",
+        glob: glob.to_string(),
+        err,
+    })? {
+        let file = file?;
+        if !file.is_file() {
+            continue;
+        }
+        let relative = file
+            .strip_prefix(root.simplified())
+            .expect("glob starts with root");
+        if !file.simple_canonicalize()?.starts_with(&canonical_root) {
+            return Err(Error::GlobOutsideProject {
+                setting: "data",
+                glob: glob.to_string(),
+                path: relative.to_path_buf(),
+            });
+        }
+        let Some(relative) = relative.to_str() else {
+            return Err(Error::NonUtf8Path(relative.to_path_buf()));
+        };
+        let relative = relative.replace('\\', "/");
+        let path = if base.is_empty() {
+            relative.clone()
+        } else {
+            relative
+                .strip_prefix(&format!("{base}/"))
+                .unwrap_or(&relative)
+                .to_string()
+        };
+        files.push((relative, path));
+    }
+    Ok(files)
+}
+
+/// Resolve a data file destination such as `{scripts}/foo` to its path in the wheel, e.g.,
+/// `foo-1.0.0.data/scripts/foo`.
+///
+/// The destination must start with one of the [`DATA_CATEGORIES`] as template variable, followed
+/// by a relative path.
+fn resolve_data_destination(
+    data_dir: &str,
+    file: &str,
+    destination: &str,
+) -> Result<String, Error> {
+    let invalid = || Error::InvalidDataDestination {
+        file: file.to_string(),
+        destination: destination.to_string(),
+    };
+    let (category, path) = destination
+        .strip_prefix('{')
+        .and_then(|destination| destination.split_once('}'))
+        .ok_or_else(invalid)?;
+    if !DATA_CATEGORIES.contains(&category) {
+        return Err(invalid());
+    }
+    let path = path.strip_prefix('/').ok_or_else(invalid)?;
+    if path.contains(['{', '}', '\\'])
+        || path
+            .split('/')
+            .any(|component| matches!(component, "" | "." | ".."))
+    {
+        return Err(invalid());
+    }
+    Ok(format!("{data_dir}/{category}/{path}"))
+}
+
+/// The Python interpreter used for checking the syntax of the packaged files.
+const PYTHON: &str = if cfg!(windows) { "python" } else { "python3" };
+
+/// Compile each file with Python's `compile`, reporting the first syntax error as
+/// `<index>\t<line>\t<message>`.
+const CHECK_SYNTAX_SCRIPT: &str = r"
+import sys
+
+for index, path in enumerate(sys.argv[1:]):
+    with open(path, 'rb') as f:
+        source = f.read()
+    try:
+        compile(source, path, 'exec', dont_inherit=True)
+    except SyntaxError as err:
+        print(index, err.lineno or 0, err.msg, sep='\t')
+        sys.exit(1)
+";
+
+/// Byte-compile the Python files with an interpreter, failing on the first syntax error.
+async fn check_python_syntax(files: &[PathBuf], root: &Path) -> Result<(), Error> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    debug!("Checking the syntax of {} Python files", files.len());
+    let output = tokio::process::Command::new(PYTHON)
+        .arg("-c")
+        .arg(CHECK_SYNTAX_SCRIPT)
+        .args(files)
+        .output()
+        .await
+        .map_err(Error::PythonCheck)?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut fields = stdout.trim_end().splitn(3, '\t');
+    let (Some(index), Some(line), Some(message)) = (fields.next(), fields.next(), fields.next())
+    else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::PythonCheck(io::Error::other(format!(
+            "{PYTHON} exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ))));
+    };
+    let path = index
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| files.get(index))
+        .map(|path| path.strip_prefix(root).unwrap_or(path).to_path_buf())
+        .unwrap_or_default();
+    Err(Error::Syntax {
+        path,
+        line: line.parse().unwrap_or_default(),
+        message: message.to_string(),
+    })
+}
+
+/// Version control directories, which are always excluded from the wheel.
+const VCS_DIRS: &[&str] = &[".git", ".hg", ".svn", ".bzr"];
+
+/// Whether the entry is a version control directory, e.g., `.git`.
+fn is_vcs_dir(entry: &walkdir::DirEntry) -> bool {
+    entry.file_type().is_dir()
+        && entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| VCS_DIRS.contains(&name))
+}
+
+/// Whether the entry is a `__pycache__` directory, which is always excluded from the wheel.
+fn is_pycache_dir(entry: &walkdir::DirEntry) -> bool {
+    entry.file_type().is_dir() && entry.file_name() == "__pycache__"
+}
+
+/// Whether the file is Python bytecode, i.e., `.pyc` or `.pyo`, which is always excluded from
+/// the wheel since installers compile the sources.
+fn is_bytecode_file(relative: &Path) -> bool {
+    relative
+        .extension()
+        .is_some_and(|extension| extension == "pyc" || extension == "pyo")
+}
+
+/// Whether the file is a compiled Python extension module, i.e., `.so` or `.pyd`.
+fn is_compiled_extension(relative: &Path) -> bool {
+    relative
+        .extension()
+        .is_some_and(|extension| extension == "so" || extension == "pyd")
+}
+
+/// Whether the path matches one of the common test file conventions: `test_*.py`, `*_test.py`,
+/// `conftest.py` or a file inside a `tests` directory.
+fn is_test_file(relative: &Path) -> bool {
+    let in_tests_dir = relative.parent().is_some_and(|parent| {
+        parent
+            .components()
+            .any(|component| component.as_os_str() == "tests")
+    });
+    if in_tests_dir {
+        return true;
+    }
+
+    let Some(filename) = relative.file_name().and_then(|filename| filename.to_str()) else {
+        return false;
+    };
+    filename == "conftest.py"
+        || (filename.starts_with("test_") && filename.ends_with(".py"))
+        || filename.ends_with("_test.py")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::{formatdoc, indoc};
+    use tempfile::TempDir;
+
+    /// Create a project with the given `pyproject.toml` and files, relative to the project root.
+    fn project(pyproject_toml: &str, files: &[&str]) -> TempDir {
+        let project = TempDir::new().unwrap();
+        fs_err::write(project.path().join("pyproject.toml"), pyproject_toml).unwrap();
+        for file in files {
+            let path = project.path().join(file);
+            fs_err::create_dir_all(path.parent().unwrap()).unwrap();
+            fs_err::write(path, "").unwrap();
+        }
+        project
+    }
+
+    /// Build the wheel without any [`BuildOptions`].
+    async fn build_default(source_tree: &Path, wheel_dir: &Path) -> Result<BuildResult, Error> {
+        build(source_tree, wheel_dir, BuildOptions::default()).await
+    }
+
+    /// The paths of all entries in a wheel, in archive order.
+    fn wheel_contents(wheel: &Path) -> Vec<String> {
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(wheel).unwrap()).unwrap();
+        (0..archive.len())
+            .map(|index| archive.by_index(index).unwrap().name().to_string())
+            .collect()
+    }
+
+    const PYPROJECT_TOML: &str = indoc! {r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+
+        [build-system]
+        requires = ["uv>=0.4.15,<5"]
+        build-backend = "uv"
+    "#};
+
+    #[tokio::test]
+    async fn unix_host_system() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel = fs_err::read(output.path().join(filename.to_string())).unwrap();
+
+        // The upper byte of "version made by" in each central directory file header
+        // (signature `PK\x01\x02`) is the host system, where 3 is Unix.
+        let hosts: Vec<u8> = wheel
+            .windows(4)
+            .enumerate()
+            .filter(|(_, window)| *window == b"PK\x01\x02")
+            .map(|(offset, _)| wheel[offset + 5])
+            .collect();
+        assert_eq!(hosts, [3, 3, 3, 3]);
+    }
+
+    #[tokio::test]
+    async fn nested_readme() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            readme = "docs/README.md"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        fs_err::write(project.path().join("docs/README.md"), "# Foo\n").unwrap();
+        let output = TempDir::new().unwrap();
+
+        // The tests run in the crate directory, the readme path must be resolved relative to the
+        // project instead.
+        assert_ne!(std::env::current_dir().unwrap(), project.path());
+        let dist_info_dir = metadata(project.path(), output.path(), &[]).await.unwrap();
+        let metadata =
+            fs_err::read_to_string(output.path().join(dist_info_dir).join("METADATA")).unwrap();
+        assert!(
+            metadata.contains("Description-Content-Type: text/markdown; charset=UTF-8\n\n# Foo\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_readme() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            readme = "docs/README.md"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let err = metadata(project.path(), output.path(), &[])
+            .await
+            .unwrap_err();
+        match err {
+            Error::Validation(ValidationError::MissingReadme(path)) => {
+                assert_eq!(path, project.path().join("docs/README.md"));
+            }
+            err => panic!("Expected a missing readme error, found: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn exclude_tests_by_default() {
+        let project = project(
+            PYPROJECT_TOML,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/conftest.py",
+                "src/foo/test_foo.py",
+                "src/foo/bar_test.py",
+                "src/foo/tests/helpers.py",
+                "src/foo/testing.py",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo/testing.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn include_tests() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            include-tests = true
+        "#};
+        let project = project(
+            pyproject_toml,
+            &["src/foo/__init__.py", "src/foo/test_foo.py"],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo/test_foo.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn check_syntax() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            check-syntax = true
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        fs_err::write(
+            project.path().join("src/foo/broken.py"),
+            "import os\n\ndef broken(:\n    pass\n",
+        )
+        .unwrap();
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        match err {
+            Error::Syntax { path, line, .. } => {
+                assert_eq!(path, Path::new("src/foo/broken.py"));
+                assert_eq!(line, 3);
+            }
+            err => panic!("Expected a syntax error, found: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn prebuilt_metadata_directory() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let metadata_directory = TempDir::new().unwrap();
+        let dist_info_dir = metadata(project.path(), metadata_directory.path(), &[])
+            .await
+            .unwrap();
+        assert_eq!(dist_info_dir, "foo-1.0.0.dist-info");
+        // Mark the prepared metadata, so we can check that it's copied and not regenerated.
+        let prepared = metadata_directory
+            .path()
+            .join(&dist_info_dir)
+            .join("METADATA");
+        let mut contents = fs_err::read_to_string(&prepared).unwrap();
+        contents.push_str("Prepared\n");
+        fs_err::write(&prepared, &contents).unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                metadata_directory: Some(metadata_directory.path()),
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .unwrap()
+        .filename;
+        let wheel = output.path().join(filename.to_string());
+        assert_eq!(
+            wheel_contents(&wheel),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(&wheel).unwrap()).unwrap();
+        let metadata =
+            io::read_to_string(archive.by_name("foo-1.0.0.dist-info/METADATA").unwrap()).unwrap();
+        assert_eq!(metadata, contents);
+    }
+
+    #[tokio::test]
+    async fn stale_record_in_metadata_directory() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let metadata_directory = TempDir::new().unwrap();
+        let dist_info_dir = metadata(project.path(), metadata_directory.path(), &[])
+            .await
+            .unwrap();
+        let output = TempDir::new().unwrap();
+
+        // A `RECORD` that matches the packaged files is accepted and replaced.
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let record = wheel_file(
+            &output.path().join(filename.to_string()),
+            "foo-1.0.0.dist-info/RECORD",
+        );
+        let record_path = metadata_directory
+            .path()
+            .join(&dist_info_dir)
+            .join("RECORD");
+        fs_err::write(&record_path, &record).unwrap();
+        let filename = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                metadata_directory: Some(metadata_directory.path()),
+                output_policy: OutputPolicy::Overwrite,
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .unwrap()
+        .filename;
+        let wheel = output.path().join(filename.to_string());
+        assert_eq!(wheel_file(&wheel, "foo-1.0.0.dist-info/RECORD"), record);
+
+        // After changing the sources, the `RECORD` is outdated.
+        fs_err::write(project.path().join("src/foo/__init__.py"), "x = 1\n").unwrap();
+        let err = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                metadata_directory: Some(metadata_directory.path()),
+                output_policy: OutputPolicy::Overwrite,
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        let Error::StaleRecord(path, InstalledLayoutError::HashMismatch { path: file, .. }) = err
+        else {
+            panic!("Expected a stale `RECORD`, found: {err}");
+        };
+        assert_eq!(path, record_path);
+        assert_eq!(file, "foo/__init__.py");
+    }
+
+    #[tokio::test]
+    async fn mismatched_metadata_directory() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let metadata_directory = TempDir::new().unwrap();
+        fs_err::create_dir(metadata_directory.path().join("bar-1.0.0.dist-info")).unwrap();
+        let output = TempDir::new().unwrap();
+
+        let err = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                metadata_directory: Some(metadata_directory.path()),
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        match err {
+            Error::MissingDistInfo { expected, .. } => {
+                assert_eq!(expected, "foo-1.0.0.dist-info");
+            }
+            err => panic!("Expected a missing dist-info error, found: {err}"),
+        }
+    }
+
+    /// Build into an output directory that already contains a stale `foo-1.0.0-py3-none-any.whl`.
+    async fn build_with_existing_output(
+        output_policy: OutputPolicy,
+    ) -> (TempDir, Result<WheelFilename, Error>) {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+        fs_err::write(output.path().join("foo-1.0.0-py3-none-any.whl"), "stale").unwrap();
+        let result = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                output_policy,
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .map(|result| result.filename);
+        (output, result)
+    }
+
+    #[tokio::test]
+    async fn existing_output_overwrite() {
+        let (output, result) = build_with_existing_output(OutputPolicy::Overwrite).await;
+        let filename = result.unwrap();
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn existing_output_error() {
+        let (output, result) = build_with_existing_output(OutputPolicy::Error).await;
+        match result.unwrap_err() {
+            Error::OutputExists(path) => {
+                assert_eq!(path, output.path().join("foo-1.0.0-py3-none-any.whl"));
+            }
+            err => panic!("Expected an output exists error, found: {err}"),
+        }
+        let contents =
+            fs_err::read_to_string(output.path().join("foo-1.0.0-py3-none-any.whl")).unwrap();
+        assert_eq!(contents, "stale");
+    }
+
+    #[tokio::test]
+    async fn existing_output_skip() {
+        let (output, result) = build_with_existing_output(OutputPolicy::Skip).await;
+        let filename = result.unwrap();
+        assert_eq!(filename.to_string(), "foo-1.0.0-py3-none-any.whl");
+        let contents = fs_err::read_to_string(output.path().join(filename.to_string())).unwrap();
+        assert_eq!(contents, "stale");
+    }
+
+    #[tokio::test]
+    async fn custom_temp_dir() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let filename = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                temp_dir: Some(temp_dir.path()),
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .unwrap()
+        .filename;
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+
+        // The temporary file was moved out of the temporary directory, and nothing else was
+        // written to the output directory.
+        assert_eq!(fs_err::read_dir(temp_dir.path()).unwrap().count(), 0);
+        assert_eq!(fs_err::read_dir(output.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn package_data() {
+        let project = project(
+            PYPROJECT_TOML,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/py.typed",
+                "src/foo/templates/page.html",
+                "src/foo/templates/partials/header.html",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        // Data files keep their path relative to the package, so `importlib.resources` finds them.
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo/py.typed",
+                "foo/templates/page.html",
+                "foo/templates/partials/header.html",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn build_many_concurrently() {
+        let foo = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let bar = project(
+            &PYPROJECT_TOML.replace("name = \"foo\"", "name = \"bar\""),
+            &["src/bar/__init__.py"],
+        );
+        let output = TempDir::new().unwrap();
+
+        let results = build_many(
+            &[foo.path(), bar.path()],
+            output.path(),
+            BuildOptions::default(),
+            2,
+        )
+        .await;
+        let results: Vec<BuildResult> = results.into_iter().map(Result::unwrap).collect();
+        let filenames: Vec<String> = results
+            .iter()
+            .map(|result| result.filename.to_string())
+            .collect();
+        assert_eq!(
+            filenames,
+            ["foo-1.0.0-py3-none-any.whl", "bar-1.0.0-py3-none-any.whl"]
+        );
+        assert_eq!(
+            wheel_contents(&output.path().join(&filenames[0])),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+        assert_eq!(
+            wheel_contents(&output.path().join(&filenames[1])),
+            [
+                "bar/__init__.py",
+                "bar-1.0.0.dist-info/METADATA",
+                "bar-1.0.0.dist-info/WHEEL",
+                "bar-1.0.0.dist-info/RECORD"
+            ]
+        );
+        // Each result lists the files of its own wheel.
+        for (result, filename) in results.iter().zip(&filenames) {
+            let files: Vec<&str> = result.files.iter().map(|file| file.path.as_str()).collect();
+            assert_eq!(files, wheel_contents(&output.path().join(filename)));
+        }
+    }
+
+    #[tokio::test]
+    async fn metadata_blocking() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            description = "A project"
+            readme = "README.md"
+            dependencies = ["anyio>=4,<5"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py", "README.md"]);
+        let output = TempDir::new().unwrap();
+
+        let dist_info_dir = metadata(project.path(), output.path(), &[]).await.unwrap();
+        let expected =
+            fs_err::read_to_string(output.path().join(dist_info_dir).join("METADATA")).unwrap();
+        assert_eq!(metadata_string_blocking(project.path()).unwrap(), expected);
+    }
+
+    #[test]
+    fn canonical_metadata_independent_of_field_order() {
+        let first = project(
+            indoc! {r#"
+                [project]
+                name = "foo"
+                version = "1.0.0"
+                description = "A project"
+                readme = "README.md"
+                keywords = ["demo"]
+                dependencies = ["anyio>=4,<5"]
+
+                [project.urls]
+                Homepage = "https://example.org"
+
+                [build-system]
+                requires = ["uv>=0.4.15,<5"]
+                build-backend = "uv"
+            "#},
+            &["src/foo/__init__.py"],
+        );
+        fs_err::write(
+            first.path().join("README.md"),
+            "# Foo  \r\n\r\nA project.\r\n\r\n",
+        )
+        .unwrap();
+        let second = project(
+            indoc! {r#"
+                [build-system]
+                build-backend = "uv"
+                requires = ["uv>=0.4.15,<5"]
+
+                [project]
+                dependencies = ["anyio>=4,<5"]
+                readme = "README.md"
+                keywords = ["demo"]
+                version = "1.0.0"
+                description = "A project"
+                name = "foo"
+                urls = { Homepage = "https://example.org" }
+            "#},
+            &["src/foo/__init__.py"],
+        );
+        fs_err::write(second.path().join("README.md"), "# Foo\n\nA project.\n").unwrap();
+
+        let canonical = canonical_metadata(first.path()).unwrap();
+        assert_eq!(canonical, canonical_metadata(second.path()).unwrap());
+        assert!(canonical
+            .ends_with("Project-URL: Homepage, https://example.org\n\n# Foo\n\nA project.\n"));
+    }
+
+    #[tokio::test]
+    async fn exclude_vcs_directories() {
+        let project = project(
+            PYPROJECT_TOML,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/.git/config",
+                "src/foo/vendored/.hg/store/data",
+                "src/foo/vendored/__init__.py",
+                "src/foo/.svn/entries",
+                "src/foo/.bzr/branch-format",
+                // Only directories are excluded.
+                "src/foo/.gitignore",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert_eq!(
+            contents,
+            [
+                "foo/.gitignore",
+                "foo/__init__.py",
+                "foo/vendored/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn exclude_bytecode() {
+        let project = project(
+            PYPROJECT_TOML,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/mod.py",
+                "src/foo/__pycache__/mod.cpython-312.pyc",
+                "src/foo/__pycache__/notes.txt",
+                "src/foo/stray.pyc",
+                "src/foo/legacy.pyo",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo/mod.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn compiled_extension_in_any_wheel() {
+        let project = project(
+            PYPROJECT_TOML,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/_speedups.cpython-312-x86_64-linux-gnu.so",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The wheel is tagged for any platform, but contains a compiled extension: `foo/_speedups.cpython-312-x86_64-linux-gnu.so`"
+        );
+    }
+
+    #[tokio::test]
+    async fn license_files() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            license = "MIT"
+            license-files = ["LICENSE", "licenses/*.txt"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(
+            pyproject_toml,
+            &["src/foo/__init__.py", "LICENSE", "licenses/THIRD_PARTY.txt"],
+        );
+        fs_err::write(project.path().join("LICENSE"), "MIT License").unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel = output.path().join(filename.to_string());
+        assert_eq!(
+            wheel_contents(&wheel),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/licenses/LICENSE",
+                "foo-1.0.0.dist-info/licenses/licenses/THIRD_PARTY.txt",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+        assert_eq!(
+            wheel_file(&wheel, "foo-1.0.0.dist-info/licenses/LICENSE"),
+            "MIT License"
+        );
+
+        // A glob that matches nothing is likely a typo.
+        fs_err::remove_file(project.path().join("licenses/THIRD_PARTY.txt")).unwrap();
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            std::error::Error::source(&err).unwrap().to_string(),
+            "The `project.license-files` glob doesn't match any files: `licenses/*.txt`"
+        );
+    }
+
+    #[tokio::test]
+    async fn namespace() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "acme-plugin"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            namespace = "acme.plugin"
+        "#};
+        let project = project(
+            pyproject_toml,
+            &[
+                "src/acme/plugin/__init__.py",
+                "src/acme/plugin/core.py",
+                "src/acme/README.md",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "acme/plugin/__init__.py",
+                "acme/plugin/core.py",
+                "acme_plugin-1.0.0.dist-info/METADATA",
+                "acme_plugin-1.0.0.dist-info/WHEEL",
+                "acme_plugin-1.0.0.dist-info/RECORD"
+            ]
+        );
+
+        // The namespace must be a module name.
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            pyproject_toml.replace("acme.plugin", "acme/plugin"),
+        )
+        .unwrap();
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`tool.uv.build-backend.namespace` must be a dotted module name such as `acme.plugin`, not `acme/plugin`"
+        );
+    }
+
+    #[tokio::test]
+    async fn license_dir() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            license = "MIT"
+            license-files = ["LICENSE"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            license-dir = "root"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py", "LICENSE"]);
+        fs_err::write(project.path().join("LICENSE"), "MIT License").unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel = output.path().join(filename.to_string());
+        assert_eq!(
+            wheel_contents(&wheel),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/LICENSE",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+        let record = wheel_file(&wheel, "foo-1.0.0.dist-info/RECORD");
+        let line =
+            "foo-1.0.0.dist-info/LICENSE,sha256=q7KJbcPCqUAvkBuI1QNc8Kg9XPfBfnNkLN9WKwudO8U,11";
+        assert!(record.lines().any(|record_line| record_line == line));
+
+        // Write the license files to both locations.
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            pyproject_toml.replace(r#"license-dir = "root""#, r#"license-dir = "both""#),
+        )
+        .unwrap();
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel = output.path().join(filename.to_string());
+        assert_eq!(
+            wheel_contents(&wheel),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/licenses/LICENSE",
+                "foo-1.0.0.dist-info/LICENSE",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+        let record = wheel_file(&wheel, "foo-1.0.0.dist-info/RECORD");
+        for path in [
+            "foo-1.0.0.dist-info/licenses/LICENSE",
+            "foo-1.0.0.dist-info/LICENSE",
+        ] {
+            let line = format!("{path},sha256=q7KJbcPCqUAvkBuI1QNc8Kg9XPfBfnNkLN9WKwudO8U,11");
+            assert!(record.lines().any(|record_line| record_line == line));
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn license_file_outside_project() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            license = "MIT"
+            license-files = ["LICENSE"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let outside = TempDir::new().unwrap();
+        fs_err::write(outside.path().join("secret"), "").unwrap();
+        std::os::unix::fs::symlink(
+            outside.path().join("secret"),
+            project.path().join("LICENSE"),
+        )
+        .unwrap();
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert!(std::error::Error::source(&err)
+            .unwrap()
+            .to_string()
+            .starts_with("License files must be inside the project directory: "));
+    }
+
+    #[tokio::test]
+    async fn content_addressed() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let wheel = build_content_addressed(project.path(), output.path(), BuildOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(wheel.filename.to_string(), "foo-1.0.0-py3-none-any.whl");
+        let content_hash = wheel
+            .content_addressed
+            .strip_prefix("foo-1.0.0-py3-none-any.")
+            .and_then(|name| name.strip_suffix(".whl"))
+            .unwrap();
+        assert_eq!(content_hash.len(), CONTENT_HASH_LENGTH);
+        assert!(content_hash.chars().all(|c| c.is_ascii_hexdigit()));
+
+        let canonical = fs_err::read(output.path().join(wheel.filename.to_string())).unwrap();
+        let content_addressed = fs_err::read(output.path().join(&wheel.content_addressed)).unwrap();
+        assert_eq!(canonical, content_addressed);
+    }
+
+    /// Create a file whose name is not valid UTF-8, which Linux file systems allow.
+    #[cfg(target_os = "linux")]
+    fn non_utf8_project(pyproject_toml: &str) -> TempDir {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let name = OsStr::from_bytes(b"data-\xff.txt");
+        fs_err::write(project.path().join("src").join("foo").join(name), "").unwrap();
+        project
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn non_utf8_filename_error() {
+        let project = non_utf8_project(PYPROJECT_TOML);
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "File names in wheels must be valid UTF-8: `foo/data-\u{FFFD}.txt`"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn non_utf8_filename_skip() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            non-utf8-filenames = "skip"
+        "#};
+        let project = non_utf8_project(pyproject_toml);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert_eq!(
+            contents,
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    /// Read a file from a wheel.
+    fn wheel_file(wheel: &Path, path: &str) -> String {
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(wheel).unwrap()).unwrap();
+        io::read_to_string(archive.by_name(path).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn record_base64_by_default() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let record = wheel_file(
+            &output.path().join(filename.to_string()),
+            "foo-1.0.0.dist-info/RECORD",
+        );
+        // The hash of the empty `__init__.py` is urlsafe base64 without padding.
+        let init_py = record.lines().next().unwrap();
+        assert_eq!(
+            init_py,
+            "foo/__init__.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0"
+        );
+        assert!(record.ends_with("foo-1.0.0.dist-info/RECORD,,\n"));
+    }
+
+    #[tokio::test]
+    async fn reproducible() {
+        let project = project(
+            PYPROJECT_TOML,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/bar.py",
+                "src/foo/data/config.json",
+            ],
+        );
+        let mut wheels = Vec::new();
+        for _ in 0..2 {
+            let output = TempDir::new().unwrap();
+            let filename = build_default(project.path(), output.path())
+                .await
+                .unwrap()
+                .filename;
+            wheels.push(fs_err::read(output.path().join(filename.to_string())).unwrap());
+        }
+        assert_eq!(wheels[0], wheels[1]);
+    }
+
+    #[tokio::test]
+    async fn write_reader_size_mismatch() {
+        let output = TempDir::new().unwrap();
+        let file = fs_err::tokio::File::create(output.path().join("foo.whl"))
+            .await
+            .unwrap();
+        let mut writer = AsyncZipWriter::new(file, CompressionLevel::Default);
+
+        // A reader that delivers fewer bytes than the file had when it was opened.
+        let mut truncated: &[u8] = b"print";
+        let err = writer
+            .write_reader("foo/__init__.py", &mut truncated, 13)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected 13 bytes for `foo/__init__.py`, but read 5 bytes, was the file modified during the build?"
+        );
+        assert!(writer.record.is_empty());
+
+        // A reader that delivers more bytes, e.g., a file that grew while the wheel was written.
+        let mut grown: &[u8] = b"print('foo')\nprint('bar')\n";
+        let err = writer
+            .write_reader("foo/__init__.py", &mut grown, 13)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected 13 bytes for `foo/__init__.py`, but read 26 bytes, was the file modified during the build?"
+        );
+        assert!(writer.record.is_empty());
+
+        let mut complete: &[u8] = b"print('foo')\n";
+        writer
+            .write_reader("foo/__init__.py", &mut complete, 13)
+            .await
+            .unwrap();
+        assert_eq!(writer.record[0].size, 13);
+    }
+
+    #[test]
+    fn zip_date_time_range() {
+        assert_eq!(zip_date_time(0), zip_date_time(ZIP_EPOCH));
+        assert_eq!(zip_date_time(i64::MAX), zip_date_time(ZIP_END));
+        assert_ne!(zip_date_time(1_700_000_000), zip_date_time(ZIP_EPOCH));
+    }
+
+    #[tokio::test]
+    async fn record_hashes() {
+        let project = project(
+            PYPROJECT_TOML,
+            &["src/foo/__init__.py", "src/foo/bar/__init__.py"],
+        );
+        fs_err::write(project.path().join("src/foo/bar/__init__.py"), "x = 1\n").unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+
+        assert_record_matches(&output.path().join(filename.to_string()));
+    }
+
+    /// Check that every file in the wheel decompresses and matches its `RECORD` entry.
+    fn assert_record_matches(wheel: &Path) {
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(wheel).unwrap()).unwrap();
+        let record =
+            io::read_to_string(archive.by_name("foo-1.0.0.dist-info/RECORD").unwrap()).unwrap();
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(record.as_bytes());
+        let mut listed = Vec::new();
+        for line in reader.records() {
+            let line = line.unwrap();
+            let (path, hash, size) = (&line[0], &line[1], &line[2]);
+            listed.push(path.to_string());
+            let mut bytes = Vec::new();
+            archive
+                .by_name(path)
+                .unwrap()
+                .read_to_end(&mut bytes)
+                .unwrap();
+            if path == "foo-1.0.0.dist-info/RECORD" {
+                assert_eq!((hash, size), ("", ""));
+            } else {
+                assert_eq!(hash, hash_digest(&bytes), "{path}");
+                assert_eq!(size, bytes.len().to_string(), "{path}");
+            }
+        }
+        // Every file in the wheel is listed.
+        assert_eq!(listed, wheel_contents(wheel));
+    }
+
+    #[tokio::test]
+    async fn source_fingerprint() {
+        let fingerprint = |compression_level: &str, source: &str| {
+            let pyproject_toml = formatdoc! {r#"
+                [project]
+                name = "foo"
+                version = "1.0.0"
+
+                [build-system]
+                requires = ["uv>=0.4.15,<5"]
+                build-backend = "uv"
+
+                [tool.uv.build-backend]
+                source-fingerprint = true
+                compression-level = "{compression_level}"
+            "#};
+            let project = project(&pyproject_toml, &["src/foo/__init__.py", "src/foo/bar.py"]);
+            fs_err::write(project.path().join("src/foo/bar.py"), source).unwrap();
+            async move {
+                let output = TempDir::new().unwrap();
+                let filename = build_default(project.path(), output.path())
+                    .await
+                    .unwrap()
+                    .filename;
+                let build_metadata: serde_json::Value = serde_json::from_str(&wheel_file(
+                    &output.path().join(filename.to_string()),
+                    "foo-1.0.0.dist-info/uv_build_metadata.json",
+                ))
+                .unwrap();
+                build_metadata["source_fingerprint"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            }
+        };
+
+        // The fingerprint is independent of the compression.
+        let stored = fingerprint("stored", "a = 1\n").await;
+        let best = fingerprint("best", "a = 1\n").await;
+        assert!(stored.starts_with("sha256:"), "{stored}");
+        assert_eq!(stored, best);
+
+        let changed = fingerprint("stored", "a = 2\n").await;
+        assert_ne!(stored, changed);
+    }
+
+    #[tokio::test]
+    async fn compression_level() {
+        let build_size = |compression_level: &str| {
+            let pyproject_toml = formatdoc! {r#"
+                [project]
+                name = "foo"
+                version = "1.0.0"
+
+                [build-system]
+                requires = ["uv>=0.4.15,<5"]
+                build-backend = "uv"
+
+                [tool.uv.build-backend]
+                compression-level = "{compression_level}"
+            "#};
+            let project = project(&pyproject_toml, &["src/foo/__init__.py"]);
+            fs_err::write(
+                project.path().join("src/foo/__init__.py"),
+                "print('hello world')\n".repeat(1000),
+            )
+            .unwrap();
+            async move {
+                let output = TempDir::new().unwrap();
+                let filename = build_default(project.path(), output.path())
+                    .await
+                    .unwrap()
+                    .filename;
+                fs_err::metadata(output.path().join(filename.to_string()))
+                    .unwrap()
+                    .len()
+            }
+        };
+
+        let stored = build_size("stored").await;
+        let best = build_size("best").await;
+        assert!(stored > best, "{stored} <= {best}");
+    }
+
+    #[tokio::test]
+    async fn compression_level_fast() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            compression-level = "fast"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py", "src/foo/bar.py"]);
+        let contents = "print('hello world')\n".repeat(1000);
+        fs_err::write(project.path().join("src/foo/bar.py"), &contents).unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel = output.path().join(filename.to_string());
+
+        // The wheel is compressed, and still decompresses to the files listed in `RECORD`.
+        assert!(fs_err::metadata(&wheel).unwrap().len() < contents.len() as u64);
+        assert_eq!(wheel_file(&wheel, "foo/bar.py"), contents);
+        assert_record_matches(&wheel);
+    }
+
+    #[tokio::test]
+    async fn build_from_string() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        // Only the passed manifest is used.
+        fs_err::remove_file(project.path().join("pyproject.toml")).unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_from_pyproject_toml(PYPROJECT_TOML, project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(filename.to_string(), "foo-1.0.0-py3-none-any.whl");
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn build_result_files() {
+        let project = project(
+            PYPROJECT_TOML,
+            &["src/foo/__init__.py", "src/foo/data.json"],
+        );
+        fs_err::write(project.path().join("src/foo/data.json"), "{}").unwrap();
+        let output = TempDir::new().unwrap();
+
+        let result = build_default(project.path(), output.path()).await.unwrap();
+        let wheel = output.path().join(result.filename.to_string());
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(&wheel).unwrap()).unwrap();
+        let entries: Vec<ArchivedFile> = (0..archive.len())
+            .map(|index| {
+                let file = archive.by_index(index).unwrap();
+                ArchivedFile {
+                    path: file.name().to_string(),
+                    size: file.size(),
+                }
+            })
+            .collect();
+        assert_eq!(result.files, entries);
+        assert_eq!(
+            result.total_size,
+            entries.iter().map(|entry| entry.size).sum::<u64>()
+        );
+        assert_eq!(result.files[1].path, "foo/data.json");
+        assert_eq!(result.files[1].size, 2);
+    }
+
+    #[tokio::test]
+    async fn config_settings() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            compression-level = "best"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let contents = "print('hello world')\n".repeat(1000);
+        fs_err::write(project.path().join("src/foo/__init__.py"), &contents).unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_with_config_settings(
+            project.path(),
+            output.path(),
+            &["compression-level=stored".to_string()],
+        )
+        .await
+        .unwrap()
+        .filename;
+        let wheel = output.path().join(filename.to_string());
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(&wheel).unwrap()).unwrap();
+        let file = archive.by_name("foo/__init__.py").unwrap();
+        assert_eq!(file.compression(), zip::CompressionMethod::Stored);
+
+        let err = build_with_config_settings(
+            project.path(),
+            output.path(),
+            &["compression=stored".to_string()],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown config setting, `tool.uv.build-backend` has no setting `compression`"
+        );
+        let err = build_with_config_settings(
+            project.path(),
+            output.path(),
+            &["glob-options.case-insensitive=true".to_string()],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown config setting, `tool.uv.build-backend` has no setting `glob-options.case-insensitive`"
+        );
+        // Only table settings have nested keys.
+        let err = build_with_config_settings(
+            project.path(),
+            output.path(),
+            &["compression-level.x=best".to_string()],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown config setting, `tool.uv.build-backend` has no setting `compression-level.x`"
+        );
+        let err =
+            build_with_config_settings(project.path(), output.path(), &["strict".to_string()])
+                .await
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid config setting, expected `<key>=<value>`: `strict`"
+        );
+    }
+
+    #[tokio::test]
+    async fn record_hex() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            record-hash-encoding = "hex"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let record = wheel_file(
+            &output.path().join(filename.to_string()),
+            "foo-1.0.0.dist-info/RECORD",
+        );
+        let init_py = record.lines().next().unwrap();
+        assert_eq!(
+            init_py,
+            "foo/__init__.py,sha256=e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855,0"
+        );
+    }
+
+    #[tokio::test]
+    async fn editable_source_roots() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            source-roots = ["src", "generated"]
+        "#};
+        let project = project(
+            pyproject_toml,
+            &["src/foo/__init__.py", "generated/foo_generated/__init__.py"],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_editable(project.path(), output.path()).await.unwrap();
+        let wheel = output.path().join(filename.to_string());
+        assert_eq!(
+            wheel_contents(&wheel),
+            [
+                "foo.pth",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+        let pth = wheel_file(&wheel, "foo.pth");
+        let roots: Vec<PathBuf> = pth.lines().map(PathBuf::from).collect();
+        assert_eq!(
+            roots,
+            [
+                project.path().join("src").simple_canonicalize().unwrap(),
+                project
+                    .path()
+                    .join("generated")
+                    .simple_canonicalize()
+                    .unwrap()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn editable_missing_source_root() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            source-roots = ["src", "generated"]
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let err = build_editable(project.path(), output.path())
+            .await
+            .unwrap_err();
+        match err {
+            Error::MissingSourceRoot(path) => {
+                assert_eq!(path, project.path().join("generated"));
+            }
+            err => panic!("Expected a missing source root error, found: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn wheel_entry_points() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [project.scripts]
+            foo = "foo.cli:main"
+            foo-admin = "foo.admin:main"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(
+            pyproject_toml,
+            &["src/foo/__init__.py", "src/foo/cli.py", "src/foo/admin.py"],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let entry_points =
+            read_wheel_entry_points(&output.path().join(filename.to_string())).unwrap();
+        assert_eq!(
+            entry_points,
+            EntryPoints::from([(
+                "console_scripts".to_string(),
+                BTreeMap::from([
+                    ("foo".to_string(), "foo.cli:main".to_string()),
+                    ("foo-admin".to_string(), "foo.admin:main".to_string()),
+                ])
+            )])
+        );
+
+        // Without scripts, there is no `entry_points.txt`.
+        fs_err::write(project.path().join("pyproject.toml"), PYPROJECT_TOML).unwrap();
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let entry_points =
+            read_wheel_entry_points(&output.path().join(filename.to_string())).unwrap();
+        assert!(entry_points.is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid_script() {
+        for (script, expected) in [
+            (
+                r#""my tool" = "foo:main""#,
+                "Entrypoint names must consist of letters, numbers, underscores, dots and dashes; \
+                invalid name: `my tool`",
+            ),
+            (
+                r#""" = "foo:main""#,
+                "Entrypoint names must consist of letters, numbers, underscores, dots and dashes; \
+                invalid name: ``",
+            ),
+            (
+                r#"foo = """#,
+                "Invalid object reference for entry point `foo` in `console_scripts`, expected \
+                `<module>:<attribute>`: ``",
+            ),
+        ] {
+            let pyproject_toml = formatdoc! {r#"
+                [project]
+                name = "foo"
+                version = "1.0.0"
+
+                [project.scripts]
+                {script}
+
+                [build-system]
+                requires = ["uv>=0.4.15,<5"]
+                build-backend = "uv"
+            "#};
+            let project = project(&pyproject_toml, &["src/foo/__init__.py"]);
+            let output = TempDir::new().unwrap();
+
+            let err = build_default(project.path(), output.path())
+                .await
+                .unwrap_err();
+            assert_eq!(
+                std::error::Error::source(&err).unwrap().to_string(),
+                expected
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn entry_points_file() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [project.scripts]
+            foo = "foo.cli:main"
+
+            [project.entry-points."foo.plugins"]
+            inline = "foo.plugins:inline"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            entry-points-file = "entry_points.ini"
+        "#};
+        let project = project(
+            pyproject_toml,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/cli.py",
+                "src/foo/plugins.py",
+            ],
+        );
+        fs_err::write(
+            project.path().join("entry_points.ini"),
+            indoc! {"
+                # Migrated from setuptools.
+                [console_scripts]
+                foo-admin = foo.cli:admin
+
+                [foo.plugins]
+                from-file = foo.plugins:from_file
+            "},
+        )
+        .unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let entry_points =
+            read_wheel_entry_points(&output.path().join(filename.to_string())).unwrap();
+        assert_eq!(
+            entry_points,
+            EntryPoints::from([
+                (
+                    "console_scripts".to_string(),
+                    BTreeMap::from([
+                        ("foo".to_string(), "foo.cli:main".to_string()),
+                        ("foo-admin".to_string(), "foo.cli:admin".to_string()),
+                    ])
+                ),
+                (
+                    "foo.plugins".to_string(),
+                    BTreeMap::from([
+                        ("from-file".to_string(), "foo.plugins:from_file".to_string()),
+                        ("inline".to_string(), "foo.plugins:inline".to_string()),
+                    ])
+                )
+            ])
+        );
+
+        // Lines outside of a group are not entry points.
+        fs_err::write(
+            project.path().join("entry_points.ini"),
+            "foo-admin = foo.cli:admin\n",
+        )
+        .unwrap();
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        let err = format!("{err}: {}", std::error::Error::source(&err).unwrap());
+        assert!(
+            err.ends_with("entry_points.ini`: Invalid line in `entry_points.txt`: `foo-admin = foo.cli:admin`"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn build_log() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            build-log = true
+            record-hash-encoding = "hex"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+
+        let log =
+            fs_err::read_to_string(output.path().join("foo-1.0.0-py3-none-any.build-log.jsonl"))
+                .unwrap();
+        let events: Vec<serde_json::Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(events.iter().all(|event| event["timestamp"].is_string()));
+        let summary: Vec<String> = events
+            .iter()
+            .map(|event| {
+                let detail = event
+                    .get("name")
+                    .or_else(|| event.get("path"))
+                    .or_else(|| event.get("filename"))
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default();
+                format!("{} {detail}", event["event"].as_str().unwrap())
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                "start",
+                "resolved foo",
+                "step write-sources",
+                "step write-metadata",
+                "file foo/__init__.py",
+                "file foo-1.0.0.dist-info/METADATA",
+                "file foo-1.0.0.dist-info/WHEEL",
+                "step write-record",
+                "step persist",
+                "warning",
+                "finish foo-1.0.0-py3-none-any.whl",
+            ]
+        );
+
+        // The log is next to the wheel, not in it.
+        let contents = wheel_contents(&output.path().join(filename.to_string()));
+        assert!(!contents.iter().any(|path| path.ends_with(".jsonl")));
+    }
+
+    #[tokio::test]
+    async fn build_log_sizes() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            build-log = true
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        fs_err::write(
+            project.path().join("src/foo/__init__.py"),
+            "print('hello world')\n".repeat(100),
+        )
+        .unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+
+        let log =
+            fs_err::read_to_string(output.path().join("foo-1.0.0-py3-none-any.build-log.jsonl"))
+                .unwrap();
+        let finish: serde_json::Value = serde_json::from_str(log.lines().last().unwrap()).unwrap();
+
+        let wheel = output.path().join(filename.to_string());
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(&wheel).unwrap()).unwrap();
+        let uncompressed: u64 = (0..archive.len())
+            .map(|index| archive.by_index(index).unwrap().size())
+            .sum();
+        assert_eq!(finish["uncompressed_size"], uncompressed);
+        assert_eq!(
+            finish["compressed_size"],
+            fs_err::metadata(&wheel).unwrap().len()
+        );
+        // The repetitive source file compresses well.
+        assert!(uncompressed > fs_err::metadata(&wheel).unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn build_log_write_error() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            build-log = true
+            max-file-size = 10
+            strict = true
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        fs_err::write(
+            project.path().join("src/foo/__init__.py"),
+            "print('hello world')\n",
+        )
+        .unwrap();
+        let output = TempDir::new().unwrap();
+        // A directory in place of the log, so writing the log fails.
+        fs_err::create_dir(output.path().join("foo-1.0.0-py3-none-any.build-log.jsonl")).unwrap();
+
+        // The build error is returned, not the error writing the log.
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn max_file_size() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            max-file-size = 1000
+        "#};
+        let project = project(
+            pyproject_toml,
+            &["src/foo/__init__.py", "src/foo/data/dataset.csv"],
+        );
+        fs_err::write(
+            project.path().join("src/foo/data/dataset.csv"),
+            "1,2,3\n".repeat(1000),
+        )
+        .unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+
+        // The warning names the file and its size.
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            format!("{pyproject_toml}strict = true\n"),
+        )
+        .unwrap();
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        let Error::Validation(err) = err else {
+            panic!("{err}");
+        };
+        assert_eq!(
+            err.to_string(),
+            "Skipping `foo/data/dataset.csv` (6000 bytes), it is larger than \
+            `tool.uv.build-backend.max-file-size = 1000` (warnings are errors because \
+            `tool.uv.build-backend.strict` is set)"
+        );
+    }
+
+    #[tokio::test]
+    async fn max_file_size_before_checks() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            max-file-size = 1000
+            check-syntax = true
+        "#};
+        let project = project(
+            pyproject_toml,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/generated.py",
+                "src/foo/_native.so",
+            ],
+        );
+        // Neither the syntax error nor the compiled extension fail the build, the files are
+        // skipped before they are checked.
+        fs_err::write(
+            project.path().join("src/foo/generated.py"),
+            format!("{}def broken(:\n", "# padding\n".repeat(200)),
+        )
+        .unwrap();
+        fs_err::write(project.path().join("src/foo/_native.so"), vec![0; 2000]).unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    /// A project whose `foo/sub` package is a symlink to a directory outside `src`.
+    #[cfg(unix)]
+    fn symlinked_package_project(pyproject_toml: &str) -> TempDir {
+        let project = project(
+            pyproject_toml,
+            &[
+                "src/foo/__init__.py",
+                "vendor/real/__init__.py",
+                "vendor/real/module.py",
+            ],
+        );
+        std::os::unix::fs::symlink(
+            project.path().join("vendor/real"),
+            project.path().join("src/foo/sub"),
+        )
+        .unwrap();
+        project
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlinked_package_directory() {
+        let project = symlinked_package_project(PYPROJECT_TOML);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo/sub/__init__.py",
+                "foo/sub/module.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlinked_package_directory_error() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            symlinks = "error"
+        "#};
+        let project = symlinked_package_project(pyproject_toml);
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Symlinks are not allowed by `tool.uv.build-backend.symlinks`: `foo/sub`"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlink_cycle() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        // `foo/loop` points back to `foo`, so `foo/loop/loop/...` would never end.
+        std::os::unix::fs::symlink(".", project.path().join("src/foo/loop")).unwrap();
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Symlink cycle in the module: `foo/loop`");
+    }
+
+    #[tokio::test]
+    async fn max_walk_depth() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            max-walk-depth = 1
+        "#};
+        let project = project(
+            pyproject_toml,
+            &["src/foo/__init__.py", "src/foo/bar/__init__.py"],
+        );
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The module is nested deeper than `tool.uv.build-backend.max-walk-depth = 1`: `foo/bar/__init__.py`"
+        );
+    }
+
+    #[tokio::test]
+    async fn wheel_info_auto_detect() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel_info = wheel_file(
+            &output.path().join(filename.to_string()),
+            "foo-1.0.0.dist-info/WHEEL",
+        );
+        assert_eq!(
+            wheel_info,
+            formatdoc! {"
+                Wheel-Version: 1.0
+                Generator: uv {}
+                Root-Is-Purelib: true
+                Tag: py3-none-any
+            ", uv_version::version()}
+        );
+    }
+
+    #[tokio::test]
+    async fn wheel_info_tags_match_filename() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel_info = wheel_file(
+            &output.path().join(filename.to_string()),
+            "foo-1.0.0.dist-info/WHEEL",
+        );
+        let tags: Vec<&str> = wheel_info
+            .lines()
+            .filter_map(|line| line.strip_prefix("Tag: "))
+            .collect();
+
+        // Expand the compressed tag set of the filename as it is on disk.
+        let parsed = WheelFilename::from_str(&filename.to_string()).unwrap();
+        let mut expected = Vec::new();
+        for python_tag in &parsed.python_tag {
+            for abi_tag in &parsed.abi_tag {
+                for platform_tag in &parsed.platform_tag {
+                    expected.push(format!("{python_tag}-{abi_tag}-{platform_tag}"));
+                }
+            }
+        }
+        assert_eq!(tags, expected);
+    }
+
+    #[tokio::test]
+    async fn wheel_info_root_is_purelib_override() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            root-is-purelib = false
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel_info = wheel_file(
+            &output.path().join(filename.to_string()),
+            "foo-1.0.0.dist-info/WHEEL",
+        );
+        assert!(wheel_info.contains("Root-Is-Purelib: false\n"));
+    }
+
+    /// Write a `.tar.gz` source distribution of the project with a `PKG-INFO`, with `top_level`
+    /// as the top-level directory.
+    async fn sdist(project: &Path, top_level: &str, output: &Path) -> PathBuf {
+        use tokio::io::AsyncWriteExt;
+
+        fs_err::write(
+            project.join("PKG-INFO"),
+            metadata_string_blocking(project).unwrap(),
+        )
+        .unwrap();
+        let sdist_path = output.join(format!("{top_level}.tar.gz"));
+        let file = fs_err::tokio::File::create(&sdist_path).await.unwrap();
+        let mut builder =
+            tokio_tar::Builder::new(async_compression::tokio::write::GzipEncoder::new(file));
+        builder.append_dir_all(top_level, project).await.unwrap();
+        let mut encoder = builder.into_inner().await.unwrap();
+        encoder.shutdown().await.unwrap();
+        sdist_path
+    }
+
+    #[tokio::test]
+    async fn wheel_from_sdist() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+        let sdist_path = sdist(project.path(), "foo-1.0.0", output.path()).await;
+
+        let filename = build_from_sdist(&sdist_path, output.path()).await.unwrap();
+        assert_eq!(filename.to_string(), "foo-1.0.0-py3-none-any.whl");
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn build_sdist_roundtrip() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            readme = "README.md"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(
+            pyproject_toml,
+            &[
+                "README.md",
+                "src/foo/__init__.py",
+                "src/foo/__pycache__/__init__.cpython-312.pyc",
+                "src/foo/.git/config",
+                "docs/index.md",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_sdist(project.path(), output.path()).await.unwrap();
+        assert_eq!(filename.to_string(), "foo-1.0.0.tar.gz");
+
+        let sdist_path = output.path().join(filename.to_string());
+        let extracted = TempDir::new().unwrap();
+        uv_extract::stream::untar_gz(
+            fs_err::tokio::File::open(&sdist_path).await.unwrap(),
+            extracted.path(),
+        )
+        .await
+        .unwrap();
+        let files: Vec<String> = WalkDir::new(extracted.path())
+            .sort_by_file_name()
+            .into_iter()
+            .map(Result::unwrap)
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(extracted.path())
+                    .unwrap()
+                    .portable_display()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            files,
+            [
+                "foo-1.0.0/PKG-INFO",
+                "foo-1.0.0/README.md",
+                "foo-1.0.0/pyproject.toml",
+                "foo-1.0.0/src/foo/__init__.py"
+            ]
+        );
+
+        let wheel = build_from_sdist(&sdist_path, output.path()).await.unwrap();
+        assert_eq!(wheel.to_string(), "foo-1.0.0-py3-none-any.whl");
+
+        // A workspace member is built from its source distribution without the workspace, so
+        // the inherited fields must be part of the archived `pyproject.toml`.
+        let workspace = TempDir::new().unwrap();
+        fs_err::write(
+            workspace.path().join("pyproject.toml"),
+            indoc! {r#"
+                [project]
+                name = "workspace"
+                version = "2.0.0"
+                authors = [{ name = "Ferris the crab" }]
+
+                [tool.uv.workspace]
+                members = ["packages/*"]
+            "#},
+        )
+        .unwrap();
+        let member = workspace.path().join("packages/bar");
+        fs_err::create_dir_all(member.join("src/bar")).unwrap();
+        fs_err::write(member.join("src/bar/__init__.py"), "").unwrap();
+        fs_err::write(
+            member.join("pyproject.toml"),
+            indoc! {r#"
+                [project]
+                name = "bar"
+                dynamic = ["version", "authors"]
+
+                [build-system]
+                requires = ["uv>=0.4.15,<5"]
+                build-backend = "uv"
+
+                [tool.uv.build-backend]
+                inherit = ["version", "authors"]
+            "#},
+        )
+        .unwrap();
+
+        let filename = build_sdist(&member, output.path()).await.unwrap();
+        assert_eq!(filename.to_string(), "bar-2.0.0.tar.gz");
+        let sdist_path = output.path().join(filename.to_string());
+        let extracted = TempDir::new().unwrap();
+        uv_extract::stream::untar_gz(
+            fs_err::tokio::File::open(&sdist_path).await.unwrap(),
+            extracted.path(),
+        )
+        .await
+        .unwrap();
+        let archived: toml::Table = toml::from_str(
+            &fs_err::read_to_string(extracted.path().join("bar-2.0.0/pyproject.toml")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(archived["project"]["version"].as_str(), Some("2.0.0"));
+        assert_eq!(
+            archived["project"]["authors"][0]["name"].as_str(),
+            Some("Ferris the crab")
+        );
+        assert!(archived["project"].get("dynamic").is_none());
+        assert!(archived["tool"]["uv"]["build-backend"]
+            .get("inherit")
+            .is_none());
+
+        let wheel = build_from_sdist(&sdist_path, output.path()).await.unwrap();
+        assert_eq!(wheel.to_string(), "bar-2.0.0-py3-none-any.whl");
+
+        // The legacy license file is read when building the wheel, so it must be packaged.
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "baz"
+            version = "1.0.0"
+            license = { file = "LICENSE.txt" }
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(pyproject_toml, &["LICENSE.txt", "src/baz/__init__.py"]);
+        fs_err::write(project.path().join("LICENSE.txt"), "Baz license\n").unwrap();
+
+        let filename = build_sdist(project.path(), output.path()).await.unwrap();
+        let sdist_path = output.path().join(filename.to_string());
+        let extracted = TempDir::new().unwrap();
+        uv_extract::stream::untar_gz(
+            fs_err::tokio::File::open(&sdist_path).await.unwrap(),
+            extracted.path(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            fs_err::read_to_string(extracted.path().join("baz-1.0.0/LICENSE.txt")).unwrap(),
+            "Baz license\n"
+        );
+
+        let wheel = build_from_sdist(&sdist_path, output.path()).await.unwrap();
+        let metadata = wheel_file(
+            &output.path().join(wheel.to_string()),
+            "baz-1.0.0.dist-info/METADATA",
+        );
+        assert!(metadata.contains("License: Baz license"), "{metadata}");
+    }
+
+    #[tokio::test]
+    async fn build_sdist_version_source() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            dynamic = ["version"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            version-source = "file:VERSION"
+        "#};
+        let project = project(pyproject_toml, &["VERSION", "src/foo/__init__.py"]);
+        fs_err::write(project.path().join("VERSION"), "1.2.3\n").unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_sdist(project.path(), output.path()).await.unwrap();
+        assert_eq!(filename.to_string(), "foo-1.2.3.tar.gz");
+        let sdist_path = output.path().join(filename.to_string());
+        let extracted = TempDir::new().unwrap();
+        uv_extract::stream::untar_gz(
+            fs_err::tokio::File::open(&sdist_path).await.unwrap(),
+            extracted.path(),
+        )
+        .await
+        .unwrap();
+        // The `VERSION` file isn't packaged, the version is static instead.
+        assert!(!extracted.path().join("foo-1.2.3/VERSION").exists());
+        let archived: toml::Table = toml::from_str(
+            &fs_err::read_to_string(extracted.path().join("foo-1.2.3/pyproject.toml")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(archived["project"]["version"].as_str(), Some("1.2.3"));
+        assert!(archived["project"].get("dynamic").is_none());
+        assert!(archived["tool"]["uv"]["build-backend"]
+            .get("version-source")
+            .is_none());
+
+        let wheel = build_from_sdist(&sdist_path, output.path()).await.unwrap();
+        assert_eq!(wheel.to_string(), "foo-1.2.3-py3-none-any.whl");
+    }
+
+    #[tokio::test]
+    async fn sdist_pkg_info_matches_metadata() {
+        /// Build the source distribution and the wheel, and read their metadata.
+        async fn pkg_info_and_metadata(project: &Path, output: &Path) -> (String, String) {
+            let sdist = build_sdist(project, output).await.unwrap();
+            let extracted = TempDir::new().unwrap();
+            uv_extract::stream::untar_gz(
+                fs_err::tokio::File::open(output.join(sdist.to_string()))
+                    .await
+                    .unwrap(),
+                extracted.path(),
+            )
+            .await
+            .unwrap();
+            let pkg_info =
+                fs_err::read_to_string(extracted.path().join("foo-1.0.0/PKG-INFO")).unwrap();
+            let wheel = build_default(project, output).await.unwrap().filename;
+            let metadata = wheel_file(
+                &output.join(wheel.to_string()),
+                "foo-1.0.0.dist-info/METADATA",
+            );
+            (pkg_info, metadata)
+        }
+
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            description = "A foo package"
+            readme = "README.md"
+            requires-python = ">=3.9"
+            dependencies = ["anyio>=4; python_version >= '3.10'"]
+
+            [project.optional-dependencies]
+            cli = ["click"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(pyproject_toml, &["README.md", "src/foo/__init__.py"]);
+        fs_err::write(project.path().join("README.md"), "# Foo\n\nHello world.\n").unwrap();
+        let output = TempDir::new().unwrap();
+
+        let (pkg_info, metadata) = pkg_info_and_metadata(project.path(), output.path()).await;
+        assert_eq!(pkg_info, metadata);
+
+        // The source distribution needs at least 2.2 for the static fields to be trusted.
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            format!("{pyproject_toml}\n[tool.uv.build-backend]\nmetadata-version = \"2.1\"\n"),
+        )
+        .unwrap();
+        let (pkg_info, metadata) = pkg_info_and_metadata(project.path(), output.path()).await;
+        assert!(
+            metadata.starts_with("Metadata-Version: 2.1\n"),
+            "{metadata}"
+        );
+        assert_eq!(
+            pkg_info,
+            metadata.replace("Metadata-Version: 2.1\n", "Metadata-Version: 2.2\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn sdist_mismatched_top_level() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+        let sdist_path = sdist(project.path(), "foo-2.0.0", output.path()).await;
+
+        let err = build_from_sdist(&sdist_path, output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Invalid source distribution `{}`: expected a top-level `foo-1.0.0` directory, \
+                found `foo-2.0.0`",
+                sdist_path.user_display()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn stubs_only() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo-stubs"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            stubs = true
+        "#};
+        let project = project(
+            pyproject_toml,
+            &[
+                "src/foo-stubs/__init__.pyi",
+                "src/foo-stubs/helper.py",
+                "src/foo-stubs/py.typed",
+                "src/foo-stubs/sub/module.pyi",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(filename.to_string(), "foo_stubs-1.0.0-py3-none-any.whl");
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo-stubs/__init__.pyi",
+                "foo-stubs/py.typed",
+                "foo-stubs/sub/module.pyi",
+                "foo_stubs-1.0.0.dist-info/METADATA",
+                "foo_stubs-1.0.0.dist-info/WHEEL",
+                "foo_stubs-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[test]
+    fn shared_runtime_concurrency_limit() {
+        let projects: Vec<TempDir> = (0..3)
+            .map(|_| project(PYPROJECT_TOML, &["src/foo/__init__.py"]))
+            .collect();
+        let source_trees: Vec<&Path> = projects.iter().map(TempDir::path).collect();
+        let outputs: Vec<TempDir> = (0..2).map(|_| TempDir::new().unwrap()).collect();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let semaphore = Semaphore::new(2);
+
+        runtime.block_on(async {
+            // While all permits are taken, no build can start.
+            let held = semaphore.acquire_many(2).await.unwrap();
+            let release = async {
+                for _ in 0..10 {
+                    tokio::task::yield_now().await;
+                }
+                assert_eq!(fs_err::read_dir(outputs[0].path()).unwrap().count(), 0);
+                drop(held);
+            };
+            let (first, second, ()) = tokio::join!(
+                build_many_limited(
+                    &source_trees,
+                    outputs[0].path(),
+                    BuildOptions::default(),
+                    &semaphore
+                ),
+                build_many_limited(
+                    &source_trees[..1],
+                    outputs[1].path(),
+                    BuildOptions::default(),
+                    &semaphore
+                ),
+                release,
+            );
+            assert!(first.iter().chain(&second).all(Result::is_ok));
+        });
+        assert_eq!(semaphore.available_permits(), 2);
+
+        // A synchronous build on the same runtime.
+        let output = TempDir::new().unwrap();
+        let filename = build_on(
+            runtime.handle(),
+            source_trees[0],
+            output.path(),
+            BuildOptions::default(),
+        )
+        .unwrap()
+        .filename;
+        assert!(output.path().join(filename.to_string()).is_file());
+
+        semaphore.close();
+        let results = runtime.block_on(build_many_limited(
+            &source_trees[..1],
+            output.path(),
+            BuildOptions::default(),
+            &semaphore,
+        ));
+        assert!(matches!(results[..], [Err(Error::SemaphoreClosed)]));
+    }
+
+    #[test]
+    fn wheel_directory_precedence() {
+        let explicit = Path::new("/explicit");
+        let from_env = PathBuf::from("/mounted");
+        let relative_to = RelativeWheelDirectory::default();
+
+        // The explicit directory takes precedence over the environment variable.
+        assert_eq!(
+            resolve_wheel_directory(Some(explicit), Some(from_env.clone()), relative_to),
+            Some(explicit.to_path_buf())
+        );
+        assert_eq!(
+            resolve_wheel_directory(None, Some(from_env.clone()), relative_to),
+            Some(from_env)
+        );
+        assert_eq!(
+            resolve_wheel_directory(None, Some(PathBuf::new()), relative_to),
+            None
+        );
+        assert_eq!(resolve_wheel_directory(None, None, relative_to), None);
+    }
+
+    #[tokio::test]
+    async fn relative_wheel_directory() {
+        let project = project(PYPROJECT_TOML, &["src/foo/__init__.py"]);
+        let relative = Path::new("dist");
+
+        // By default, relative paths are left to the current directory.
+        assert_eq!(
+            resolve_wheel_directory(
+                Some(relative),
+                Some(project.path().to_path_buf()),
+                RelativeWheelDirectory::CurrentDirectory,
+            ),
+            Some(relative.to_path_buf())
+        );
+        // Without a project directory, there is nothing else to resolve against.
+        assert_eq!(
+            resolve_wheel_directory(
+                Some(relative),
+                None,
+                RelativeWheelDirectory::ProjectDirectory
+            ),
+            Some(relative.to_path_buf())
+        );
+
+        let wheel_dir = resolve_wheel_directory(
+            Some(relative),
+            Some(project.path().to_path_buf()),
+            RelativeWheelDirectory::ProjectDirectory,
+        )
+        .unwrap();
+        assert_eq!(wheel_dir, project.path().join("dist"));
+        fs_err::create_dir_all(&wheel_dir).unwrap();
+        let filename = build_default(project.path(), &wheel_dir)
+            .await
+            .unwrap()
+            .filename;
+        assert!(project
+            .path()
+            .join("dist")
+            .join(filename.to_string())
+            .is_file());
+
+        // Absolute paths are never changed.
+        let absolute = wheel_dir.join("absolute");
+        assert_eq!(
+            resolve_wheel_directory(
+                Some(&absolute),
+                Some(project.path().to_path_buf()),
+                RelativeWheelDirectory::ProjectDirectory,
+            ),
+            Some(absolute)
+        );
+    }
+
+    #[tokio::test]
+    async fn data_files() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend.data-files]
+            "bin/foo-script" = "{scripts}/foo-script"
+            "share/foo.conf" = "{data}/etc/foo/foo.conf"
+        "#};
+        let project = project(
+            pyproject_toml,
+            &["src/foo/__init__.py", "bin/foo-script", "share/foo.conf"],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.data/scripts/foo-script",
+                "foo-1.0.0.data/data/etc/foo/foo.conf",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn metadata_overlay() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            description = "A foo"
+            classifiers = ["Programming Language :: Python"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let overlay = [
+            (
+                "Classifier".to_string(),
+                "Private :: Do Not Upload".to_string(),
+            ),
+            ("summary".to_string(), "A custom foo build".to_string()),
+        ];
+        let filename = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                metadata_overlay: &overlay,
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .unwrap()
+        .filename;
+        let metadata = wheel_file(
+            &output.path().join(filename.to_string()),
+            "foo-1.0.0.dist-info/METADATA",
+        );
+        assert_eq!(
+            metadata,
+            indoc! {"
+                Metadata-Version: 2.3
+                Name: foo
+                Version: 1.0.0
+                Summary: A custom foo build
+                Classifier: Programming Language :: Python
+                Classifier: Private :: Do Not Upload
+            "}
+        );
+
+        // The fields that determine the filename can't be overlaid.
+        let overlay = [("Name".to_string(), "bar".to_string())];
+        let err = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                output_policy: OutputPolicy::Overwrite,
+                metadata_overlay: &overlay,
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            std::error::Error::source(&err).unwrap().to_string(),
+            "The metadata overlay can't set `Name`"
+        );
+
+        let overlay = [(
+            "Requires-Dist".to_string(),
+            "not a requirement!".to_string(),
+        )];
+        let err = build(
+            project.path(),
+            output.path(),
+            BuildOptions {
+                output_policy: OutputPolicy::Overwrite,
+                metadata_overlay: &overlay,
+                ..BuildOptions::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            std::error::Error::source(&err).unwrap().to_string(),
+            "Invalid `Requires-Dist` in the metadata overlay: `not a requirement!`"
+        );
+    }
+
+    #[tokio::test]
+    async fn script_permissions() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend.data-files]
+            "bin/foo-script" = "{scripts}/foo-script"
+            "share/foo.conf" = "{data}/etc/foo/foo.conf"
+        "#};
+        let project = project(
+            pyproject_toml,
+            &["src/foo/__init__.py", "bin/foo-script", "share/foo.conf"],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel = output.path().join(filename.to_string());
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(&wheel).unwrap()).unwrap();
+        let mut mode = |path| {
+            archive
+                .by_name(path)
+                .unwrap()
+                .unix_mode()
+                .map(|mode| mode & 0o777)
+        };
+        assert_eq!(mode("foo-1.0.0.data/scripts/foo-script"), Some(0o755));
+        assert_eq!(mode("foo-1.0.0.data/data/etc/foo/foo.conf"), Some(0o644));
+        assert_eq!(mode("foo/__init__.py"), Some(0o644));
+    }
+
+    #[tokio::test]
+    async fn data_globs() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend.data]
+            scripts = ["bin/*"]
+            headers = ["include/foo/*.h"]
+            data = ["share/man/**/*.1"]
+        "#};
+        let project = project(
+            pyproject_toml,
+            &[
+                "src/foo/__init__.py",
+                "bin/foo-script",
+                "include/foo/foo.h",
+                "include/foo/foo.c",
+                "share/man/man1/foo.1",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel = output.path().join(filename.to_string());
+        assert_eq!(
+            wheel_contents(&wheel),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.data/data/man1/foo.1",
+                "foo-1.0.0.data/headers/foo.h",
+                "foo-1.0.0.data/scripts/foo-script",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(&wheel).unwrap()).unwrap();
+        let mode = archive
+            .by_name("foo-1.0.0.data/scripts/foo-script")
+            .unwrap()
+            .unix_mode()
+            .map(|mode| mode & 0o777);
+        assert_eq!(mode, Some(0o755));
+
+        // Only the categories of the `.data` directory are allowed.
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            pyproject_toml.replace("headers = ", "include = "),
+        )
+        .unwrap();
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid category in `tool.uv.build-backend.data`, expected one of `purelib`, `platlib`, `headers`, `scripts`, `data`: `include`"
+        );
+    }
+
+    #[tokio::test]
+    async fn data_glob_parent_directory() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend.data]
+            data = ["share/../../*"]
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py"]);
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The `tool.uv.build-backend.data` globs must be relative to the project root: `share/../../*`"
+        );
+    }
+
+    #[tokio::test]
+    async fn glob_no_match() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            include = ["foo/**/*.py", "foo/**/*.jsno"]
+
+            [tool.uv.build-backend.data]
+            data = ["share/*.txt"]
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py", "share/foo.md"]);
+        let output = TempDir::new().unwrap();
+
+        // Without strict mode, the globs are only reported.
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            pyproject_toml.replace(
+                "[tool.uv.build-backend]\n",
+                "[tool.uv.build-backend]\nstrict = true\n",
+            ),
+        )
+        .unwrap();
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        let Error::Validation(err) = err else {
+            panic!("{err}");
+        };
+        assert_eq!(
+            err.to_string(),
+            "The `tool.uv.build-backend.include` glob doesn't match any files: `foo/**/*.jsno` \
+            (warnings are errors because `tool.uv.build-backend.strict` is set)"
+        );
+
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            pyproject_toml.replace(
+                "include = [\"foo/**/*.py\", \"foo/**/*.jsno\"]\n",
+                "strict = true\n",
+            ),
+        )
+        .unwrap();
+        let err = build_sdist(project.path(), output.path())
+            .await
+            .unwrap_err();
+        let Error::Validation(err) = err else {
+            panic!("{err}");
+        };
+        assert_eq!(
+            err.to_string(),
+            "The `tool.uv.build-backend.data` glob doesn't match any files: `share/*.txt` \
+            (warnings are errors because `tool.uv.build-backend.strict` is set)"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn data_glob_symlink_outside_project() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend.data]
+            data = ["share/*"]
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py", "share/foo.txt"]);
+        let outside = TempDir::new().unwrap();
+        fs_err::write(outside.path().join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(
+            outside.path().join("secret.txt"),
+            project.path().join("share/secret.txt"),
+        )
+        .unwrap();
+        let output = TempDir::new().unwrap();
+
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The `tool.uv.build-backend.data` glob `share/*` matches a file outside the project directory: `share/secret.txt`"
+        );
+
+        // The source distribution doesn't include it either.
+        let err = build_sdist(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The `tool.uv.build-backend.data` glob `share/*` matches a file outside the project directory: `share/secret.txt`"
+        );
+    }
+
+    #[tokio::test]
+    async fn default_file_modes() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            default-file-mode = 0o600
+            default-executable-mode = 0o700
+
+            [tool.uv.build-backend.data-files]
+            "bin/foo-script" = "{scripts}/foo-script"
+        "#};
+        let project = project(pyproject_toml, &["src/foo/__init__.py", "bin/foo-script"]);
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        let wheel = output.path().join(filename.to_string());
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(&wheel).unwrap()).unwrap();
+        let mut mode = |path| {
+            archive
+                .by_name(path)
+                .unwrap()
+                .unix_mode()
+                .map(|mode| mode & 0o777)
+        };
+        assert_eq!(mode("foo-1.0.0.data/scripts/foo-script"), Some(0o700));
+        assert_eq!(mode("foo/__init__.py"), Some(0o600));
+        assert_eq!(mode("foo-1.0.0.dist-info/RECORD"), Some(0o600));
+
+        // Files the owner can't read are a mistake.
+        fs_err::write(
+            project.path().join("pyproject.toml"),
+            pyproject_toml.replace("0o600", "0o044"),
+        )
+        .unwrap();
+        let err = build_default(project.path(), output.path())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`tool.uv.build-backend.default-file-mode` must be a mode between `0o400` and `0o777` that the owner can read, not `0o44`"
+        );
+    }
+
+    #[test]
+    fn invalid_data_destination() {
+        for destination in [
+            "etc/foo.conf",
+            "{config}/foo.conf",
+            "{data}",
+            "{data}/../foo.conf",
+            "{data}/{scripts}/foo",
+        ] {
+            let err =
+                resolve_data_destination("foo-1.0.0.data", "foo.conf", destination).unwrap_err();
+            assert!(matches!(err, Error::InvalidDataDestination { .. }), "{err}");
+        }
+        assert_eq!(
+            resolve_data_destination("foo-1.0.0.data", "foo.h", "{headers}/foo.h").unwrap(),
+            "foo-1.0.0.data/headers/foo.h"
+        );
+    }
+
+    #[tokio::test]
+    async fn flat_layout() {
+        let project = project(
+            PYPROJECT_TOML,
+            &["foo/__init__.py", "foo/bar.py", "tests/test_foo.py"],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo/bar.py",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn include_exclude() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            include = ["foo/**/*.py", "foo/data/*.json"]
+            exclude = ["foo/data/secret.json"]
+        "#};
+        let project = project(
+            pyproject_toml,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/bar/__init__.py",
+                "src/foo/bar/baz.py",
+                "src/foo/notes.md",
+                "src/foo/data/config.json",
+                "src/foo/data/secret.json",
+            ],
+        );
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo/bar/__init__.py",
+                "foo/bar/baz.py",
+                "foo/data/config.json",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn manifest() {
+        let pyproject_toml = indoc! {r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            include = ["foo/**/*.py"]
+            manifest = "MANIFEST.in"
+        "#};
+        let project = project(
+            pyproject_toml,
+            &[
+                "src/foo/__init__.py",
+                "src/foo/notes.md",
+                "src/foo/data/config.json",
+                "src/foo/data/secret.json",
+                "src/foo/data/tables/table.csv",
+                "src/foo/drafts/draft.py",
+            ],
+        );
+        fs_err::write(
+            project.path().join("MANIFEST.in"),
+            indoc! {"
+                recursive-include src/foo/data *.json *.csv
+                exclude src/foo/data/secret.json
+                prune src/foo/drafts
+            "},
+        )
+        .unwrap();
+        let output = TempDir::new().unwrap();
+
+        let filename = build_default(project.path(), output.path())
+            .await
+            .unwrap()
+            .filename;
+        assert_eq!(
+            wheel_contents(&output.path().join(filename.to_string())),
+            [
+                "foo/__init__.py",
+                "foo/data/config.json",
+                "foo/data/tables/table.csv",
+                "foo-1.0.0.dist-info/METADATA",
+                "foo-1.0.0.dist-info/WHEEL",
+                "foo-1.0.0.dist-info/RECORD"
+            ]
+        );
+    }
+}
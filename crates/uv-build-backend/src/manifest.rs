@@ -0,0 +1,190 @@
+//! A subset of the setuptools `MANIFEST.in` format, mapped onto glob patterns.
+//!
+//! <https://setuptools.pypa.io/en/latest/userguide/miscellaneous.html>
+
+use glob::{MatchOptions, Pattern, PatternError};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("Unknown directive on line {line}: `{directive}`")]
+    UnknownDirective { line: usize, directive: String },
+    #[error("Wrong number of arguments for `{directive}` on line {line}")]
+    Arguments { line: usize, directive: String },
+    #[error("Invalid pattern on line {line}: `{pattern}`")]
+    Pattern {
+        line: usize,
+        pattern: String,
+        #[source]
+        err: PatternError,
+    },
+}
+
+/// Include or exclude the files matching a pattern.
+#[derive(Debug)]
+struct Rule {
+    include: bool,
+    pattern: Pattern,
+}
+
+/// The rules of a `MANIFEST.in`-style file, in order.
+///
+/// Supported are `include`, `exclude`, `recursive-include`, `recursive-exclude`,
+/// `global-include`, `global-exclude`, `graft` and `prune`. Patterns are relative to the project
+/// root.
+#[derive(Debug, Default)]
+pub(crate) struct Manifest {
+    rules: Vec<Rule>,
+}
+
+impl Manifest {
+    /// Parse the directives, one per line, skipping blank lines and `#` comments.
+    pub(crate) fn parse(contents: &str) -> Result<Self, ManifestError> {
+        let mut rules = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let directive = words.next().expect("line is not empty");
+            let arguments: Vec<&str> = words.collect();
+            let arguments_error = || ManifestError::Arguments {
+                line: line_number,
+                directive: directive.to_string(),
+            };
+
+            // The directory arguments are matched literally, the file arguments are patterns.
+            let (include, patterns): (bool, Vec<String>) = match directive {
+                "include" | "exclude" => {
+                    if arguments.is_empty() {
+                        return Err(arguments_error());
+                    }
+                    let patterns = arguments.iter().map(ToString::to_string).collect();
+                    (directive == "include", patterns)
+                }
+                "global-include" | "global-exclude" => {
+                    if arguments.is_empty() {
+                        return Err(arguments_error());
+                    }
+                    let patterns = arguments
+                        .iter()
+                        .map(|pattern| format!("**/{pattern}"))
+                        .collect();
+                    (directive == "global-include", patterns)
+                }
+                "recursive-include" | "recursive-exclude" => {
+                    let [dir, patterns @ ..] = arguments.as_slice() else {
+                        return Err(arguments_error());
+                    };
+                    if patterns.is_empty() {
+                        return Err(arguments_error());
+                    }
+                    let dir = Pattern::escape(dir.trim_end_matches('/'));
+                    let patterns = patterns
+                        .iter()
+                        .map(|pattern| format!("{dir}/**/{pattern}"))
+                        .collect();
+                    (directive == "recursive-include", patterns)
+                }
+                "graft" | "prune" => {
+                    let [dir] = arguments.as_slice() else {
+                        return Err(arguments_error());
+                    };
+                    let dir = Pattern::escape(dir.trim_end_matches('/'));
+                    (directive == "graft", vec![format!("{dir}/**/*")])
+                }
+                _ => {
+                    return Err(ManifestError::UnknownDirective {
+                        line: line_number,
+                        directive: directive.to_string(),
+                    })
+                }
+            };
+
+            for pattern in patterns {
+                let parsed = Pattern::new(&pattern).map_err(|err| ManifestError::Pattern {
+                    line: line_number,
+                    pattern: pattern.clone(),
+                    err,
+                })?;
+                rules.push(Rule {
+                    include,
+                    pattern: parsed,
+                });
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether a file, relative to the project root, is packaged.
+    ///
+    /// Starting from `included`, the rules are applied in order, so later directives take
+    /// precedence, like in setuptools.
+    pub(crate) fn is_included(
+        &self,
+        relative: &Path,
+        included: bool,
+        options: MatchOptions,
+    ) -> bool {
+        self.rules.iter().fold(included, |included, rule| {
+            if rule.pattern.matches_path_with(relative, options) {
+                rule.include
+            } else {
+                included
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn directives() {
+        let manifest = Manifest::parse(indoc! {"
+            # Data files
+            recursive-include src/foo/data *.json *.csv
+            exclude src/foo/data/secret.json
+            prune src/foo/drafts
+            global-exclude *.bak
+        "})
+        .unwrap();
+        let options = MatchOptions::default();
+        let included =
+            |path: &str, included| manifest.is_included(Path::new(path), included, options);
+
+        assert!(included("src/foo/data/config.json", false));
+        assert!(included("src/foo/data/nested/table.csv", false));
+        assert!(!included("src/foo/data/notes.md", false));
+        assert!(!included("src/foo/data/secret.json", true));
+        assert!(!included("src/foo/drafts/draft.py", true));
+        assert!(!included("src/foo/old.py.bak", true));
+        assert!(included("src/foo/__init__.py", true));
+    }
+
+    #[test]
+    fn invalid() {
+        let err = Manifest::parse("include\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Wrong number of arguments for `include` on line 1"
+        );
+        let err = Manifest::parse("\nprune a b\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Wrong number of arguments for `prune` on line 2"
+        );
+        let err = Manifest::parse("include-package-data foo\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown directive on line 1: `include-package-data`"
+        );
+        let err = Manifest::parse("include foo/[\n").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid pattern on line 1: `foo/[`");
+    }
+}
@@ -0,0 +1,3318 @@
+use crate::pep639_glob::parse_pep639_glob;
+use crate::settings::{
+    setting_names, BuildBackendSettings, GlobOptions, MarkdownVariant, MetadataFieldOrder,
+};
+use crate::{EntryPoints, Error};
+use itertools::Itertools;
+use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{MarkerTree, Requirement, VersionOrUrl};
+use pypi_types::{Metadata23, VerbatimParsedUrl};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, PoisonError};
+use thiserror::Error;
+use tracing::debug;
+use uv_fs::Simplified;
+use uv_normalize::{ExtraName, PackageName};
+use uv_pubgrub::PubGrubSpecifier;
+use uv_warnings::warn_user_once;
+
+#[derive(Debug, Error)]
+pub enum ValidationError {
+    /// The spec isn't clear about what the values in that field would be, and we only support the
+    /// default value (UTF-8).
+    #[error("Charsets other than UTF-8 are not supported. Please convert your README to UTF-8 and remove `project.readme.charset`.")]
+    ReadmeCharset,
+    #[error("Unknown Readme extension `{0}`, can't determine content type. Please use a support extension (`.md`, `.rst`, `.txt`) or set the content type manually.")]
+    UnknownExtension(String),
+    #[error("Can't infer content type because `{}` does not have an extension. Please use a support extension (`.md`, `.rst`, `.txt`) or set the content type manually.", _0.user_display())]
+    MissingExtension(PathBuf),
+    #[error("Readme file not found: `{}`", _0.simplified_display())]
+    MissingReadme(PathBuf),
+    #[error("Unsupported content type: `{0}`")]
+    UnsupportedContentType(String),
+    #[error("Entries in `{field}` must be a single line, invalid entry: `{value}`")]
+    Newlines { field: &'static str, value: String },
+    #[error("Entries in `{field}` must be a distribution name, optionally followed by a version in parentheses and a marker, invalid entry: `{value}`")]
+    InvalidDistribution { field: &'static str, value: String },
+    #[error("Constraints must be a package name with version specifiers, invalid line: `{0}`")]
+    InvalidConstraint(String),
+    #[error(
+        "The constraints for `{name}` exclude all versions allowed by the project: `{specifiers}`"
+    )]
+    ConflictingConstraint {
+        name: PackageName,
+        specifiers: VersionSpecifiers,
+    },
+    #[error("Unknown `tool.uv.build-backend.metadata-version`, expected one of {}: `{0}`", METADATA_VERSIONS.iter().map(|version| format!("`{version}`")).join(", "))]
+    UnknownMetadataVersion(String),
+    #[error("`{field}` requires `Metadata-Version: {required}` or later, but `tool.uv.build-backend.metadata-version` is `{version}`")]
+    MetadataVersionTooLow {
+        field: &'static str,
+        required: &'static str,
+        version: String,
+    },
+    #[error("Invalid entry point group name, expected letters, numbers and underscores separated by dots: `{0}`")]
+    InvalidEntryPointGroup(String),
+    #[error("The entry point group `{0}` is reserved, use `project.scripts` or `project.gui-scripts` instead")]
+    ReservedEntryPointGroup(String),
+    #[error("Invalid object reference for entry point `{name}` in `{group}`, expected `<module>:<attribute>`: `{object_reference}`")]
+    InvalidObjectReference {
+        group: String,
+        name: String,
+        object_reference: String,
+    },
+    #[error("The module of entry point `{name}` in `{group}` doesn't exist: `{module}`")]
+    MissingEntryPointModule {
+        group: String,
+        name: String,
+        module: String,
+    },
+    #[error("The entry point `{name}` in `{group}` is defined both in `pyproject.toml` and in `tool.uv.build-backend.entry-points-file`")]
+    DuplicateEntryPoint { group: String, name: String },
+    #[error("Invalid content type parameters, expected `; <key>=<value>` pairs: `{0}`")]
+    InvalidContentTypeParameter(String),
+    #[error("Unknown content type parameter `{0}`, only `charset` and `variant` (for `text/markdown`) are supported")]
+    UnknownContentTypeParameter(String),
+    #[error("Unknown markdown variant `{0}`, expected `GFM` or `CommonMark`")]
+    MarkdownVariant(String),
+    #[error("`{field}` must be a URL, invalid value: `{value}`")]
+    InvalidUrl {
+        field: &'static str,
+        value: String,
+        #[source]
+        err: url::ParseError,
+    },
+    #[error("The `project.license-files` glob doesn't match any files: `{0}`")]
+    LicenseGlobNoMatch(String),
+    #[error("License files must be inside the project directory: `{}`", _0.user_display())]
+    LicenseFileOutsideProject(PathBuf),
+    #[error("The metadata overlay can't set `{0}`")]
+    OverlayField(String),
+    #[error("Invalid `{field}` in the metadata overlay: `{value}`")]
+    OverlayValue { field: String, value: String },
+    #[error("When `project.license-files` is defined, `project.license` must be an SPDX expression string")]
+    MixedLicenseGenerations,
+    #[error(
+        "Entrypoint names must consist of letters, numbers, underscores, dots and dashes; invalid name: `{0}`"
+    )]
+    InvalidName(String),
+    #[error("`project.{0}` can't be inherited from the workspace root, inheritable fields are: {fields}", fields = INHERITABLE_FIELDS.join(", "))]
+    NotInheritable(String),
+    #[error("`tool.uv.build-backend.inherit` is set, but there is no parent directory with a `pyproject.toml` containing `[tool.uv.workspace]`")]
+    MissingWorkspaceRoot,
+    #[error("`project.{field}` is inherited from the workspace root, but `{}` does not define it", root.user_display())]
+    MissingInheritedField { field: String, root: PathBuf },
+    #[error("`project.{0}` is required, but it is neither set in the project nor inherited from the workspace root")]
+    MissingField(&'static str),
+    #[error("{0} (warnings are errors because `tool.uv.build-backend.strict` is set)")]
+    Strict(String),
+    #[error(
+        "`project.version` must not be set when `tool.uv.build-backend.version-source` is set"
+    )]
+    DuplicateVersion,
+    #[error("Unsupported `tool.uv.build-backend.version-source`, expected `attr:<module>.<attribute>` or `file:<path>`: `{0}`")]
+    InvalidVersionSource(String),
+    #[error("The module of `tool.uv.build-backend.version-source` was not found in `src`: `{0}`")]
+    MissingVersionModule(String),
+    #[error("`{attribute}` is not assigned in `{}`", path.user_display())]
+    MissingVersionAttribute { attribute: String, path: PathBuf },
+    #[error("`{attribute}` in `{}` must be assigned a plain string literal, computed versions are not supported", path.user_display())]
+    ComputedVersion { attribute: String, path: PathBuf },
+    #[error("`{0}` is not a valid version")]
+    InvalidAttrVersion(String, #[source] pep440_rs::VersionParseError),
+    #[error("`{}` does not contain a valid version", _0.user_display())]
+    InvalidFileVersion(PathBuf, #[source] pep440_rs::VersionParseError),
+    #[error("`project.requires-python` is not a valid PEP 440 version specifier: `{0}`")]
+    InvalidRequiresPython(String, #[source] pep440_rs::VersionSpecifiersParseError),
+    #[error("`project.dynamic` contains `version`, but `tool.uv.build-backend.version-source` is not set, e.g., to `attr:<module>.__version__`")]
+    DynamicVersion,
+    #[error("`project.{field}` {reason}")]
+    Dynamic { field: String, reason: &'static str },
+}
+
+/// Check that the fields in `project.dynamic` are provided by the build backend, and that they
+/// are not also set statically.
+///
+/// Conversely, a version read through `tool.uv.build-backend.version-source` must be declared as
+/// dynamic. Inherited fields are not computed, so they don't need to be declared.
+fn check_dynamic(
+    dynamic: &[String],
+    fields: &toml::Table,
+    settings: &BuildBackendSettings,
+) -> Result<(), ValidationError> {
+    for field in dynamic {
+        if fields.contains_key(field) {
+            return Err(ValidationError::Dynamic {
+                field: field.clone(),
+                reason: "is listed in `project.dynamic`, but also set statically",
+            });
+        }
+        let provided = (field == "version" && settings.version_source.is_some())
+            || settings.inherit.contains(field);
+        if !provided {
+            if field == "version" {
+                return Err(ValidationError::DynamicVersion);
+            }
+            return Err(ValidationError::Dynamic {
+                field: field.clone(),
+                reason: "is listed in `project.dynamic`, but the uv build backend can't provide it",
+            });
+        }
+    }
+    if settings.version_source.is_some()
+        && !fields.contains_key("version")
+        && !dynamic.iter().any(|field| field == "version")
+    {
+        return Err(ValidationError::Dynamic {
+            field: "version".to_string(),
+            reason: "is read from `tool.uv.build-backend.version-source`, but it is not listed in `project.dynamic`",
+        });
+    }
+    Ok(())
+}
+
+/// The `[project]` fields a workspace member can inherit from the workspace root.
+///
+/// `name` is specific to each project, and fields describing files (`readme`, `license-files`)
+/// would be resolved relative to the member, so they are excluded.
+const INHERITABLE_FIELDS: &[&str] = &[
+    "version",
+    "authors",
+    "maintainers",
+    "license",
+    "keywords",
+    "classifiers",
+    "urls",
+    "requires-python",
+];
+
+/// The `[project]` fields that must be present after merging the inherited fields.
+const REQUIRED_FIELDS: &[&str] = &["name", "version"];
+
+/// The longest `Summary` that indexes commonly accept, PyPI rejects longer ones.
+const SUMMARY_LENGTH: usize = 512;
+
+/// A `pyproject.toml` as specified in PEP 517.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PyProjectToml {
+    /// Project metadata
+    project: Project,
+    /// Build-related data
+    build_system: BuildSystem,
+    /// Tool-specific configuration, we only read `tool.uv.build-backend`.
+    #[serde(default)]
+    tool: Tool,
+    /// The warnings shown so far, for the build log.
+    #[serde(skip)]
+    warnings: Arc<Mutex<Vec<String>>>,
+}
+
+impl PyProjectToml {
+    pub(crate) fn name(&self) -> &PackageName {
+        &self.project.name
+    }
+
+    pub(crate) fn version(&self) -> &Version {
+        &self.project.version
+    }
+
+    /// Write the core metadata, with the dependencies grouped by extra if
+    /// `tool.uv.build-backend.group-extras` is set, the extras sorted if
+    /// `tool.uv.build-backend.sort-extras` is set, and the fields in the order of
+    /// `tool.uv.build-backend.metadata-field-order`.
+    pub(crate) fn format_metadata(&self, metadata: &Metadata23) -> String {
+        let mut metadata = Cow::Borrowed(metadata);
+        if self.settings().sort_extras {
+            metadata
+                .to_mut()
+                .provides_extras
+                .sort_by_cached_key(|extra| {
+                    ExtraName::from_str(extra)
+                        .map_or_else(|_| extra.clone(), |extra| extra.to_string())
+                });
+        }
+        let group_extras = self.settings().group_extras;
+        let formatted = if group_extras {
+            metadata.core_metadata_format_grouped()
+        } else {
+            metadata.core_metadata_format()
+        };
+        match self.settings().metadata_field_order {
+            MetadataFieldOrder::Specification => {
+                specification_field_order(&formatted, group_extras)
+            }
+            MetadataFieldOrder::Legacy => formatted,
+        }
+    }
+
+    /// The readme file, if the readme is a file and not inline text.
+    pub(crate) fn readme_file(&self) -> Option<&Path> {
+        match &self.project.readme {
+            Some(Readme::String(path) | Readme::File { file: path, .. }) => Some(path),
+            Some(Readme::Text { .. }) | None => None,
+        }
+    }
+
+    /// The license file of the legacy `project.license = { file = "..." }`.
+    pub(crate) fn license_file(&self) -> Option<&Path> {
+        match &self.project.license {
+            Some(License::File { file }) => Some(file),
+            Some(License::Spdx(_) | License::Text { .. }) | None => None,
+        }
+    }
+
+    pub(crate) fn parse(contents: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// Parse the `pyproject.toml` of the project in `source_tree`, merging in the fields
+    /// inherited from the workspace root through `tool.uv.build-backend.inherit` and the version
+    /// read through `tool.uv.build-backend.version-source`.
+    pub(crate) fn parse_with_workspace(contents: &str, source_tree: &Path) -> Result<Self, Error> {
+        /// Read only the build backend settings and the dynamic fields, as the project table may
+        /// be incomplete before merging.
+        #[derive(Deserialize)]
+        struct ToolOnly {
+            #[serde(default)]
+            project: DynamicOnly,
+            #[serde(default)]
+            tool: Tool,
+        }
+
+        #[derive(Deserialize, Default)]
+        struct DynamicOnly {
+            #[serde(default)]
+            dynamic: Vec<String>,
+            /// The statically set fields.
+            #[serde(flatten)]
+            fields: toml::Table,
+        }
+
+        let ToolOnly { project, tool } = toml::from_str::<ToolOnly>(contents)?;
+        let settings = tool.uv.build_backend;
+        check_dynamic(&project.dynamic, &project.fields, &settings)?;
+        if settings.inherit.is_empty() && settings.version_source.is_none() {
+            return Self::parse(contents);
+        }
+
+        let mut pyproject_toml: toml::Table = toml::from_str(contents)?;
+        let project = pyproject_toml
+            .entry("project")
+            .or_insert(toml::Value::Table(toml::Table::new()));
+        if let Some(project) = project.as_table_mut() {
+            if let Some(version_source) = &settings.version_source {
+                if project.contains_key("version") {
+                    return Err(ValidationError::DuplicateVersion.into());
+                }
+                let version = if let Some(path) = version_source.strip_prefix("file:") {
+                    read_file_version(&source_tree.join(path.trim()))?
+                } else {
+                    read_attr_version(&settings.source_root(source_tree), version_source)?
+                };
+                debug!("Read version `{version}` from `{version_source}`");
+                project.insert("version".to_string(), version.to_string().into());
+            }
+
+            if !settings.inherit.is_empty() {
+                inherit_fields(project, &settings.inherit, source_tree)?;
+            }
+
+            for field in REQUIRED_FIELDS.iter().copied() {
+                if !project.contains_key(field) {
+                    return Err(ValidationError::MissingField(field).into());
+                }
+            }
+        }
+
+        Ok(toml::Value::Table(pyproject_toml).try_into()?)
+    }
+
+    /// Parse the `pyproject.toml` like [`PyProjectToml::parse_with_workspace`], with `key=value`
+    /// config settings overriding `tool.uv.build-backend`, mirroring PEP 517's `config_settings`.
+    ///
+    /// Keys are relative to `tool.uv.build-backend`, with a dot for the keys of the table
+    /// settings `glob-options`, `data` and `data-files`, e.g.,
+    /// `glob-options.case-sensitive=false`. Values are parsed as TOML value, falling back to a
+    /// string, so `strict=true` is a boolean and `compression-level=stored` is a string.
+    pub(crate) fn parse_with_config_settings(
+        contents: &str,
+        source_tree: &Path,
+        config_settings: &[String],
+    ) -> Result<Self, Error> {
+        if config_settings.is_empty() {
+            return Self::parse_with_workspace(contents, source_tree);
+        }
+
+        let mut pyproject_toml: toml::Table = toml::from_str(contents)?;
+        for config_setting in config_settings {
+            let Some((key, value)) = config_setting.split_once('=') else {
+                return Err(Error::InvalidConfigSetting(config_setting.clone()));
+            };
+            let key = key.trim();
+            let (setting, nested) = match key.split_once('.') {
+                Some((setting, nested)) => (setting, Some(nested)),
+                None => (key, None),
+            };
+            let known = setting_names::<BuildBackendSettings>().contains(&setting)
+                && match (setting, nested) {
+                    (_, None) => true,
+                    ("glob-options", Some(nested)) => {
+                        setting_names::<GlobOptions>().contains(&nested)
+                    }
+                    // The keys of these tables are chosen by the user.
+                    ("data" | "data-files", Some(_)) => true,
+                    // Other settings are not tables.
+                    (_, Some(_)) => false,
+                };
+            if !known {
+                return Err(Error::UnknownConfigSetting(key.to_string()));
+            }
+
+            let value = value.trim();
+            let value = toml::from_str::<toml::Table>(&format!("value = {value}"))
+                .ok()
+                .and_then(|mut table| table.remove("value"))
+                .unwrap_or_else(|| toml::Value::String(value.to_string()));
+            debug!("Overriding `tool.uv.build-backend.{key}` with config setting: `{value}`");
+
+            let mut table = &mut pyproject_toml;
+            for component in ["tool", "uv", "build-backend"]
+                .into_iter()
+                .chain(nested.map(|_| setting))
+            {
+                let entry = table
+                    .entry(component)
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                if !entry.is_table() {
+                    *entry = toml::Value::Table(toml::Table::new());
+                }
+                table = entry.as_table_mut().expect("just inserted a table");
+            }
+            table.insert(nested.unwrap_or(setting).to_string(), value);
+        }
+
+        Self::parse_with_workspace(&pyproject_toml.to_string(), source_tree)
+    }
+
+    /// The `pyproject.toml` to write into the source distribution.
+    ///
+    /// A source distribution is built without its workspace, so the inherited fields are written
+    /// into `[project]` and `tool.uv.build-backend.inherit` is removed. The version read through
+    /// `tool.uv.build-backend.version-source` is written as static version, since the file it
+    /// was read from may not be part of the source distribution. Otherwise, the file is kept as
+    /// is.
+    pub(crate) fn sdist_pyproject_toml(
+        &self,
+        contents: &str,
+        source_tree: &Path,
+    ) -> Result<String, Error> {
+        let settings = self.settings();
+        let inherit = &settings.inherit;
+        if inherit.is_empty() && settings.version_source.is_none() {
+            return Ok(contents.to_string());
+        }
+
+        let mut pyproject_toml: toml::Table = toml::from_str(contents)?;
+        if let Some(project) = pyproject_toml
+            .get_mut("project")
+            .and_then(toml::Value::as_table_mut)
+        {
+            if settings.version_source.is_some() {
+                project.insert("version".to_string(), self.version().to_string().into());
+            }
+            if !inherit.is_empty() {
+                inherit_fields(project, inherit, source_tree)?;
+            }
+            // The inherited fields and the version are static now.
+            if let Some(dynamic) = project
+                .get_mut("dynamic")
+                .and_then(toml::Value::as_array_mut)
+            {
+                dynamic.retain(|field| {
+                    !field.as_str().is_some_and(|field| {
+                        (field == "version" && settings.version_source.is_some())
+                            || inherit.iter().any(|inherited| inherited == field)
+                    })
+                });
+                if dynamic.is_empty() {
+                    project.remove("dynamic");
+                }
+            }
+        }
+        if let Some(build_backend) = pyproject_toml
+            .get_mut("tool")
+            .and_then(|tool| tool.get_mut("uv"))
+            .and_then(|uv| uv.get_mut("build-backend"))
+            .and_then(toml::Value::as_table_mut)
+        {
+            build_backend.remove("inherit");
+            build_backend.remove("version-source");
+        }
+        Ok(pyproject_toml.to_string())
+    }
+
+    /// The `[tool.uv.build-backend]` settings, or the defaults if the table is absent.
+    pub(crate) fn settings(&self) -> &BuildBackendSettings {
+        &self.tool.uv.build_backend
+    }
+
+    /// Warn if the `[build-system]` table looks suspicious.
+    ///
+    /// Example of a valid table:
+    ///
+    /// ```toml
+    /// [build-system]
+    /// requires = ["uv>=0.4.15,<5"]
+    /// build-backend = "uv"
+    /// ```
+    ///
+    /// In strict mode, the first warning is returned as error instead.
+    pub(crate) fn check_build_system(&self) -> Result<(), ValidationError> {
+        if self.build_system.build_backend.as_deref() != Some("uv") {
+            self.warn(format!(
+                r#"The value for `build_system.build-backend` should be `"uv"`, not `"{}"`"#,
+                self.build_system.build_backend.clone().unwrap_or_default()
+            ))?;
+        }
+        let uv_version =
+            Version::from_str(uv_version::version()).expect("uv's own version is valid PEP 440");
+        self.check_build_system_requires(&uv_version)
+    }
+
+    /// Check that `build-system.requires` installs a uv that is compatible with this backend.
+    fn check_build_system_requires(&self, uv_version: &Version) -> Result<(), ValidationError> {
+        let uv = PackageName::from_str("uv").expect("valid package name");
+        let Some(requirement) = self
+            .build_system
+            .requires
+            .iter()
+            .find(|requirement| requirement.name == uv)
+        else {
+            self.warn(format!(
+                "`build_system.requires` should contain `uv`, but it only contains: `{}`",
+                self.build_system.requires.iter().join("`, `")
+            ))?;
+            return Ok(());
+        };
+        if let Some(VersionOrUrl::VersionSpecifier(specifiers)) = &requirement.version_or_url {
+            if !specifiers.contains(uv_version) {
+                self.warn(format!(
+                    "`build_system.requires` contains `{requirement}`, which does not include the running version of uv ({uv_version})"
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Show a warning, or fail with it if `tool.uv.build-backend.strict` is set.
+    pub(crate) fn warn(&self, message: String) -> Result<(), ValidationError> {
+        if self.settings().strict {
+            return Err(ValidationError::Strict(message));
+        }
+        warn_user_once!("{message}");
+        self.warnings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(message);
+        Ok(())
+    }
+
+    /// The warnings shown so far.
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        self.warnings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Validate and convert a `pyproject.toml` to core metadata.
+    ///
+    /// <https://packaging.python.org/en/latest/guides/writing-pyproject-toml/>
+    /// <https://packaging.python.org/en/latest/specifications/pyproject-toml/>
+    /// <https://packaging.python.org/en/latest/specifications/core-metadata/>
+    pub(crate) fn to_metadata(&self, root: &Path) -> Result<Metadata23, Error> {
+        let settings = self.settings();
+
+        let (description, description_content_type) = match &self.project.readme {
+            Some(Readme::String(path)) => {
+                let content = read_readme(root, path)?;
+                let content_type = match path.extension().and_then(OsStr::to_str) {
+                    Some("txt") => "text/plain",
+                    Some("rst") => "text/x-rst",
+                    Some("md") => "text/markdown",
+                    Some(unknown) => {
+                        return Err(ValidationError::UnknownExtension(unknown.to_owned()).into())
+                    }
+                    None => return Err(ValidationError::MissingExtension(path.clone()).into()),
+                };
+                let content_type = description_content_type(content_type, None, settings)?;
+                (Some(content), Some(content_type))
+            }
+            Some(Readme::File {
+                file,
+                content_type,
+                charset,
+            }) => {
+                let content = read_readme(root, file)?;
+                let content_type =
+                    description_content_type(content_type, charset.as_deref(), settings)?;
+                (Some(content), Some(content_type))
+            }
+            Some(Readme::Text {
+                text,
+                content_type,
+                charset,
+            }) => {
+                let content_type =
+                    description_content_type(content_type, charset.as_deref(), settings)?;
+                (Some(text.clone()), Some(content_type))
+            }
+            None => (None, None),
+        };
+
+        let (author, author_email) = Contact::to_fields(self.project.authors.as_deref());
+        let (maintainer, maintainer_email) =
+            Contact::to_fields(self.project.maintainers.as_deref());
+
+        let (license, license_expression, license_files) = if let Some(license_globs) =
+            &self.project.license_files
+        {
+            let license_expression = match &self.project.license {
+                None => None,
+                Some(License::Spdx(license_expression)) => Some(license_expression.clone()),
+                Some(License::Text { .. } | License::File { .. }) => {
+                    return Err(ValidationError::MixedLicenseGenerations.into())
+                }
+            };
+
+            let mut license_files = Vec::new();
+            let canonical_root = root.simple_canonicalize()?;
+            for license_glob in license_globs {
+                let mut matched = false;
+                let pep639_glob = parse_pep639_glob(license_glob)
+                    .map_err(|err| Error::Pep639Glob(license_glob.to_string(), err))?;
+                let absolute_glob = PathBuf::from(glob::Pattern::escape(
+                    root.simplified().to_string_lossy().as_ref(),
+                ))
+                .join(pep639_glob.to_string())
+                .to_string_lossy()
+                .to_string();
+                for license_file in
+                    glob::glob_with(&absolute_glob, settings.glob_options.match_options())
+                        .map_err(|err| Error::Pattern(absolute_glob.to_string(), err))?
+                {
+                    let license_file = license_file.map_err(Error::Glob)?;
+                    if !license_file.is_file() {
+                        continue;
+                    }
+                    // Symlinks and absolute globs could otherwise read arbitrary files.
+                    let Ok(relative) = license_file.strip_prefix(root.simplified()) else {
+                        return Err(ValidationError::LicenseFileOutsideProject(license_file).into());
+                    };
+                    if !license_file
+                        .simple_canonicalize()?
+                        .starts_with(&canonical_root)
+                    {
+                        return Err(ValidationError::LicenseFileOutsideProject(license_file).into());
+                    }
+                    matched = true;
+                    let relative = relative.portable_display().to_string();
+                    if !license_files.contains(&relative) {
+                        license_files.push(relative);
+                    }
+                }
+                if !matched {
+                    return Err(
+                        ValidationError::LicenseGlobNoMatch(license_glob.to_string()).into(),
+                    );
+                }
+            }
+            // The glob order may depend on the order of the files on disk, which isn't
+            // reproducible, so we sort.
+            license_files.sort();
+
+            (None, license_expression, license_files)
+        } else {
+            match &self.project.license {
+                None => (None, None, Vec::new()),
+                Some(License::Spdx(license_expression)) => {
+                    (None, Some(license_expression.clone()), Vec::new())
+                }
+                Some(License::Text { text }) => {
+                    self.warn(
+                        "`project.license = { text = \"...\" }` is deprecated, use an SPDX \
+                            expression such as `license = \"MIT\"` instead"
+                            .to_string(),
+                    )?;
+                    (Some(text.clone()), None, Vec::new())
+                }
+                Some(License::File { file }) => {
+                    self.warn(
+                        "`project.license = { file = \"...\" }` is deprecated, use an SPDX \
+                            expression in `project.license` and the file in \
+                            `project.license-files` instead"
+                            .to_string(),
+                    )?;
+                    let text = fs_err::read_to_string(root.join(file))?;
+                    (Some(text), None, Vec::new())
+                }
+            }
+        };
+
+        let summary = if let Some(summary) = &self.project.description {
+            check_single_line("project.description", std::slice::from_ref(summary))?;
+            if summary.chars().count() > SUMMARY_LENGTH {
+                self.warn(format!(
+                    "`project.description` is longer than {SUMMARY_LENGTH} characters, \
+                    indexes may reject it"
+                ))?;
+            }
+            Some(summary.clone())
+        } else if settings.summary_from_readme {
+            let summary = description.as_deref().and_then(readme_summary);
+            if let Some(summary) = &summary {
+                debug!("Using the first line of the readme as summary: `{summary}`");
+            }
+            summary
+        } else {
+            None
+        };
+
+        let platforms = settings.platforms.clone();
+        check_single_line("tool.uv.build-backend.platforms", &platforms)?;
+        let supported_platforms = settings.supported_platforms.clone();
+        check_single_line(
+            "tool.uv.build-backend.supported-platforms",
+            &supported_platforms,
+        )?;
+        let requires_external = settings.requires_external.clone();
+        check_single_line(
+            "tool.uv.build-backend.requires-external",
+            &requires_external,
+        )?;
+        let provides_dist = settings.provides_dist.clone();
+        check_distributions("tool.uv.build-backend.provides-dist", &provides_dist)?;
+        let obsoletes_dist = settings.obsoletes_dist.clone();
+        check_distributions("tool.uv.build-backend.obsoletes-dist", &obsoletes_dist)?;
+
+        // TODO(konsti): https://peps.python.org/pep-0753/#label-normalization (Draft)
+        let project_urls = self
+            .project
+            .urls
+            .iter()
+            .flatten()
+            .map(|(key, value)| format!("{key}, {value}"))
+            .collect();
+
+        let (home_page, download_url) = if settings.legacy_urls {
+            (
+                self.legacy_url("Homepage", "project.urls.Homepage")?,
+                self.legacy_url("Download", "project.urls.Download")?,
+            )
+        } else {
+            (None, None)
+        };
+
+        let extras = self
+            .project
+            .optional_dependencies
+            .iter()
+            .flat_map(|optional_dependencies| optional_dependencies.keys())
+            .collect::<Vec<_>>();
+        // A dependency may be listed multiple times with different markers, each entry becomes its
+        // own `Requires-Dist` in the original order.
+        let mut requires_dist = self.project.dependencies.clone().unwrap_or_default();
+        for (extra, requirements) in self.project.optional_dependencies.iter().flatten() {
+            requires_dist.extend(
+                requirements
+                    .iter()
+                    .cloned()
+                    .map(|requirement| requirement.with_extra_marker(extra)),
+            );
+        }
+
+        if let Some(constraints) = &settings.constraints {
+            let constraints = read_constraints(&root.join(constraints))?;
+            apply_constraints(&mut requires_dist, &constraints)?;
+        }
+
+        let requires_python = if let Some(requires_python) = &self.project.requires_python {
+            // Normalize the specifiers, e.g., `>=3.8,<4` to `>=3.8, <4`.
+            let specifiers = VersionSpecifiers::from_str(requires_python).map_err(|err| {
+                ValidationError::InvalidRequiresPython(requires_python.clone(), err)
+            })?;
+            Some(specifiers.to_string())
+        } else if settings.infer_requires_python {
+            let requires_python = self
+                .project
+                .classifiers
+                .iter()
+                .flatten()
+                .filter_map(|classifier| python_classifier_version(classifier))
+                .min()
+                .map(|version| format!(">={version}"));
+            if let Some(requires_python) = &requires_python {
+                self.warn(format!(
+                    "`project.requires-python` is missing, using `{requires_python}` inferred from the classifiers"
+                ))?;
+            }
+            requires_python
+        } else {
+            None
+        };
+
+        let mut metadata = Metadata23 {
+            metadata_version: "2.3".to_string(),
+            name: self.project.name.to_string(),
+            version: self.project.version.to_string(),
+            platforms,
+            supported_platforms,
+            summary,
+            description,
+            description_content_type,
+            keywords: self
+                .project
+                .keywords
+                .as_ref()
+                .map(|keywords| keywords.join(",")),
+            // Deprecated, superseded by `Project-URL`, only written on request.
+            home_page,
+            // Deprecated, superseded by `Project-URL`, only written on request.
+            download_url,
+            author,
+            author_email,
+            maintainer,
+            maintainer_email,
+            license,
+            license_expression,
+            license_files,
+            classifiers: self.project.classifiers.clone().unwrap_or_default(),
+            requires_dist: requires_dist.iter().map(ToString::to_string).collect(),
+            provides_extras: extras.iter().map(ToString::to_string).collect(),
+            provides_dist,
+            obsoletes_dist,
+            requires_python,
+            requires_external,
+            project_urls,
+            dynamic: vec![],
+        };
+
+        if let Some(metadata_version) = &settings.metadata_version {
+            check_metadata_version(&metadata, metadata_version)?;
+            metadata.metadata_version.clone_from(metadata_version);
+        } else if let Some((_, required)) = required_metadata_version(&metadata) {
+            // PEP 639 fields are only standardized in 2.4, otherwise we stay on 2.3 for
+            // compatibility with older tools.
+            if Version::from_str(required).expect("valid version")
+                > Version::from_str(&metadata.metadata_version).expect("valid version")
+            {
+                metadata.metadata_version = required.to_string();
+            }
+        }
+
+        Ok(metadata)
+    }
+
+    /// The core metadata for the `PKG-INFO` of a source distribution.
+    ///
+    /// This is the metadata of the wheel, but with at least `Metadata-Version: 2.2`, so that tools
+    /// can trust the fields that are not marked as `Dynamic`, e.g., `Requires-Dist`, without
+    /// building the wheel (PEP 643).
+    pub(crate) fn to_sdist_metadata(&self, root: &Path) -> Result<Metadata23, Error> {
+        let mut metadata = self.to_metadata(root)?;
+        let metadata_version =
+            Version::from_str(&metadata.metadata_version).expect("valid version");
+        if metadata_version < Version::new([2, 2]) {
+            debug!(
+                "Using `Metadata-Version: 2.2` instead of `{metadata_version}` for the source distribution"
+            );
+            metadata.metadata_version = "2.2".to_string();
+        }
+        Ok(metadata)
+    }
+
+    /// The `project.urls` entry with the given label for the deprecated `Home-page` and
+    /// `Download-URL` fields, comparing labels case-insensitively.
+    fn legacy_url(
+        &self,
+        label: &str,
+        field: &'static str,
+    ) -> Result<Option<String>, ValidationError> {
+        let Some((_, url)) = self
+            .project
+            .urls
+            .iter()
+            .flatten()
+            .find(|(key, _)| key.eq_ignore_ascii_case(label))
+        else {
+            return Ok(None);
+        };
+        check_single_line(field, std::slice::from_ref(url))?;
+        if let Err(err) = url::Url::parse(url) {
+            return Err(ValidationError::InvalidUrl {
+                field,
+                value: url.clone(),
+                err,
+            });
+        }
+        Ok(Some(url.clone()))
+    }
+
+    /// Validate and convert the console scripts, GUI scripts and entry points in `pyproject.toml`
+    /// to an `entry_points.txt`.
+    ///
+    /// <https://packaging.python.org/en/latest/specifications/entry-points/>
+    ///
+    /// The entry points read from `tool.uv.build-backend.entry-points-file` are merged in, an
+    /// entry point may only be defined in one of the two places.
+    ///
+    /// Object references must be valid, and if they point into the project's own module, the
+    /// module must exist in the source root. Groups in
+    /// `tool.uv.build-backend.dynamic-entry-point-groups` are exempt from the object reference
+    /// checks, for plugin frameworks that generate the targets.
+    ///
+    /// Returns `None` if no entrypoints were defined.
+    pub(crate) fn to_entry_points(
+        &self,
+        root: &Path,
+        from_file: &EntryPoints,
+    ) -> Result<Option<String>, ValidationError> {
+        let mut groups: EntryPoints = BTreeMap::new();
+        if let Some(scripts) = &self.project.scripts {
+            groups.insert("console_scripts".to_string(), scripts.clone());
+        }
+        // On Windows, the launchers for GUI scripts don't open a console window.
+        if let Some(gui_scripts) = &self.project.gui_scripts {
+            groups.insert("gui_scripts".to_string(), gui_scripts.clone());
+        }
+        for (group, entry_points) in self.project.entry_points.iter().flatten() {
+            if group == "console_scripts" || group == "gui_scripts" {
+                return Err(ValidationError::ReservedEntryPointGroup(group.clone()));
+            }
+            groups.insert(group.clone(), entry_points.clone());
+        }
+        for (group, entry_points) in from_file {
+            let merged = groups.entry(group.clone()).or_default();
+            for (name, object_reference) in entry_points {
+                if merged
+                    .insert(name.clone(), object_reference.clone())
+                    .is_some()
+                {
+                    return Err(ValidationError::DuplicateEntryPoint {
+                        group: group.clone(),
+                        name: name.clone(),
+                    });
+                }
+            }
+        }
+
+        let dynamic_groups = &self.settings().dynamic_entry_point_groups;
+        for group in dynamic_groups {
+            if !is_entry_point_group(group) {
+                return Err(ValidationError::InvalidEntryPointGroup(group.clone()));
+            }
+            if !groups.contains_key(group) {
+                self.warn(format!(
+                    "`tool.uv.build-backend.dynamic-entry-point-groups` contains `{group}`, \
+                    which doesn't have any entry points"
+                ))?;
+            }
+        }
+
+        if groups.is_empty() {
+            return Ok(None);
+        }
+
+        // The scripts come first, as in the tables of `pyproject.toml`.
+        let scripts = ["console_scripts", "gui_scripts"];
+        let ordered = groups
+            .iter()
+            .filter(|(group, _)| scripts.contains(&group.as_str()))
+            .chain(
+                groups
+                    .iter()
+                    .filter(|(group, _)| !scripts.contains(&group.as_str())),
+            );
+
+        let mut writer = String::new();
+        for (group, entry_points) in ordered {
+            if !is_entry_point_group(group) {
+                return Err(ValidationError::InvalidEntryPointGroup(group.clone()));
+            }
+            let dynamic = dynamic_groups.contains(group);
+
+            let _ = writeln!(writer, "[{group}]");
+            for (name, object_reference) in entry_points {
+                if scripts.contains(&group.as_str()) {
+                    // More strict than the spec, we enforce the recommendation, since the name
+                    // becomes the name of an executable.
+                    if !is_entry_point_name(name) {
+                        return Err(ValidationError::InvalidName(name.to_string()));
+                    }
+                } else if name.is_empty()
+                    || name.contains(['=', '\n', '\r'])
+                    || name.starts_with('[')
+                    || name.trim() != name
+                {
+                    // Anything else would be read back as a different name.
+                    return Err(ValidationError::InvalidName(name.to_string()));
+                }
+                // Even without validating the reference, it must be a single, non-empty line.
+                if object_reference.trim().is_empty() || object_reference.contains(['\n', '\r']) {
+                    return Err(ValidationError::InvalidObjectReference {
+                        group: group.clone(),
+                        name: name.clone(),
+                        object_reference: object_reference.clone(),
+                    });
+                }
+                if dynamic {
+                    debug!("Not validating entry point `{name}` in dynamic group `{group}`");
+                } else {
+                    self.check_object_reference(group, name, object_reference, root)?;
+                }
+                let _ = writeln!(writer, "{name} = {object_reference}");
+            }
+            writer.push('\n');
+        }
+        Ok(Some(writer))
+    }
+
+    /// Check that an object reference such as `foo.cli:main` consists of Python identifiers, and
+    /// that the module exists if it is part of the project's own module.
+    fn check_object_reference(
+        &self,
+        group: &str,
+        name: &str,
+        object_reference: &str,
+        root: &Path,
+    ) -> Result<(), ValidationError> {
+        let invalid = || ValidationError::InvalidObjectReference {
+            group: group.to_string(),
+            name: name.to_string(),
+            object_reference: object_reference.to_string(),
+        };
+
+        // Extras after the object reference are deprecated, but still allowed by the spec.
+        let reference = object_reference
+            .split_once('[')
+            .map_or(object_reference, |(reference, _extras)| reference)
+            .trim();
+        let (module, attribute) = match reference.split_once(':') {
+            Some((module, attribute)) => (module.trim(), Some(attribute.trim())),
+            None => (reference, None),
+        };
+        if !module.split('.').all(is_identifier)
+            || attribute.is_some_and(|attribute| !attribute.split('.').all(is_identifier))
+        {
+            return Err(invalid());
+        }
+
+        let module_name = self.name().as_dist_info_name();
+        if module.split('.').next() != Some(&*module_name) {
+            return Ok(());
+        }
+        let module_path = module
+            .split('.')
+            .fold(self.settings().source_root(root), |path, component| {
+                path.join(component)
+            });
+        if !module_path.with_extension("py").is_file() && !module_path.join("__init__.py").is_file()
+        {
+            return Err(ValidationError::MissingEntryPointModule {
+                group: group.to_string(),
+                name: name.to_string(),
+                module: module.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Apply a metadata overlay, such as an additional build-specific classifier, to the generated
+/// core metadata.
+///
+/// The overlay takes precedence over `pyproject.toml`: Multiple-use fields, e.g., `Classifier`,
+/// are appended to, single-use fields, e.g., `Summary`, are replaced. Field names are
+/// case-insensitive. The fields that determine the wheel filename and the packaged license files
+/// can't be overlaid.
+pub(crate) fn apply_overlay(
+    metadata: &mut Metadata23,
+    overlay: &[(String, String)],
+) -> Result<(), ValidationError> {
+    for (field, value) in overlay {
+        let invalid = || ValidationError::OverlayValue {
+            field: field.clone(),
+            value: value.clone(),
+        };
+        let key = field.to_ascii_lowercase();
+        // Only the description is multi-line, it's written as message body.
+        if key != "description" && value.contains(['\n', '\r']) {
+            return Err(invalid());
+        }
+        let valid = match key.as_str() {
+            "requires-dist" => Requirement::<VerbatimParsedUrl>::from_str(value).is_ok(),
+            "requires-python" => VersionSpecifiers::from_str(value).is_ok(),
+            "provides-extra" => ExtraName::from_str(value).is_ok(),
+            "project-url" => value.split_once(", ").is_some(),
+            _ => true,
+        };
+        if !valid {
+            return Err(invalid());
+        }
+
+        let value = value.clone();
+        match key.as_str() {
+            "platform" => metadata.platforms.push(value),
+            "supported-platform" => metadata.supported_platforms.push(value),
+            "classifier" => metadata.classifiers.push(value),
+            "requires-dist" => metadata.requires_dist.push(value),
+            "provides-dist" => metadata.provides_dist.push(value),
+            "obsoletes-dist" => metadata.obsoletes_dist.push(value),
+            "requires-external" => metadata.requires_external.push(value),
+            "project-url" => metadata.project_urls.push(value),
+            "provides-extra" => metadata.provides_extras.push(value),
+            "dynamic" => metadata.dynamic.push(value),
+            "summary" => metadata.summary = Some(value),
+            "description" => metadata.description = Some(value),
+            "description-content-type" => metadata.description_content_type = Some(value),
+            "keywords" => metadata.keywords = Some(value),
+            "home-page" => metadata.home_page = Some(value),
+            "download-url" => metadata.download_url = Some(value),
+            "author" => metadata.author = Some(value),
+            "author-email" => metadata.author_email = Some(value),
+            "maintainer" => metadata.maintainer = Some(value),
+            "maintainer-email" => metadata.maintainer_email = Some(value),
+            "license" => metadata.license = Some(value),
+            "license-expression" => metadata.license_expression = Some(value),
+            "requires-python" => metadata.requires_python = Some(value),
+            _ => return Err(ValidationError::OverlayField(field.clone())),
+        }
+    }
+    Ok(())
+}
+
+/// Copy the fields listed in `tool.uv.build-backend.inherit` from the workspace root's
+/// `[project]` table, unless the project sets them itself.
+fn inherit_fields(
+    project: &mut toml::Table,
+    inherit: &[String],
+    source_tree: &Path,
+) -> Result<(), Error> {
+    if let Some(field) = inherit
+        .iter()
+        .find(|field| !INHERITABLE_FIELDS.contains(&field.as_str()))
+    {
+        return Err(ValidationError::NotInheritable(field.clone()).into());
+    }
+
+    let (workspace_root, workspace_pyproject_toml) = find_workspace_root(source_tree)?;
+    let workspace_project = workspace_pyproject_toml
+        .get("project")
+        .and_then(toml::Value::as_table)
+        .cloned()
+        .unwrap_or_default();
+
+    for field in inherit {
+        if project.contains_key(field) {
+            debug!("Not inheriting `project.{field}`, it is set in the project");
+            continue;
+        }
+        let Some(value) = workspace_project.get(field) else {
+            return Err(ValidationError::MissingInheritedField {
+                field: field.clone(),
+                root: workspace_root.join("pyproject.toml"),
+            }
+            .into());
+        };
+        debug!(
+            "Inheriting `project.{field}` from `{}`",
+            workspace_root.user_display()
+        );
+        project.insert(field.clone(), value.clone());
+    }
+    Ok(())
+}
+
+/// Read the version from a module attribute without executing Python, e.g., for
+/// `attr:foo.__version__`, read the `__version__ = "1.2.3"` assignment in `foo/__init__.py` or
+/// `foo.py` in the source root.
+///
+/// Only an assignment of a plain string literal at the top level of the module is supported,
+/// anything requiring evaluation is rejected.
+fn read_attr_version(source_root: &Path, version_source: &str) -> Result<Version, Error> {
+    let invalid_source = || ValidationError::InvalidVersionSource(version_source.to_string());
+    let attr = version_source
+        .strip_prefix("attr:")
+        .ok_or_else(invalid_source)?
+        .trim();
+    let (module, attribute) = attr.rsplit_once('.').ok_or_else(invalid_source)?;
+    if module.is_empty() || attribute.is_empty() {
+        return Err(invalid_source().into());
+    }
+
+    let module_dir = module
+        .split('.')
+        .fold(source_root.to_path_buf(), |path, part| path.join(part));
+    let package = module_dir.join("__init__.py");
+    let path = if package.is_file() {
+        package
+    } else {
+        module_dir.with_extension("py")
+    };
+    let contents = match fs_err::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(ValidationError::MissingVersionModule(module.to_string()).into());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    for line in contents.lines() {
+        let Some(rest) = line.strip_prefix(attribute) else {
+            continue;
+        };
+        // Skip annotations, e.g., `__version__: str = "1.2.3"`.
+        let rest = rest.trim_start();
+        let rest = match rest.strip_prefix(':') {
+            Some(annotated) => annotated.split_once('=').map(|(_, value)| value),
+            None => rest.strip_prefix('='),
+        };
+        // Another name with the same prefix, or a comparison.
+        let Some(value) = rest.filter(|value| !value.starts_with('=')) else {
+            continue;
+        };
+
+        let computed = || ValidationError::ComputedVersion {
+            attribute: attr.to_string(),
+            path: path.clone(),
+        };
+        let value = value.trim();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|quote| matches!(quote, '"' | '\''))
+            .ok_or_else(computed)?;
+        let (version, remainder) = value[1..].split_once(quote).ok_or_else(computed)?;
+        let remainder = remainder.trim_start();
+        if version.contains('\\') || !(remainder.is_empty() || remainder.starts_with('#')) {
+            return Err(computed().into());
+        }
+        return Version::from_str(version)
+            .map_err(|err| ValidationError::InvalidAttrVersion(attr.to_string(), err).into());
+    }
+
+    Err(ValidationError::MissingVersionAttribute {
+        attribute: attr.to_string(),
+        path,
+    }
+    .into())
+}
+
+/// Read the version from a file that contains only the version, e.g., `VERSION` with `1.2.3`.
+fn read_file_version(path: &Path) -> Result<Version, Error> {
+    let contents = fs_err::read_to_string(path)?;
+    Version::from_str(contents.trim())
+        .map_err(|err| ValidationError::InvalidFileVersion(path.to_path_buf(), err).into())
+}
+
+/// Find the closest parent directory of the source tree with a `pyproject.toml` that contains a
+/// `[tool.uv.workspace]` table, returning the directory and the parsed `pyproject.toml`.
+fn find_workspace_root(source_tree: &Path) -> Result<(PathBuf, toml::Table), Error> {
+    let source_tree = std::path::absolute(source_tree)?;
+    for directory in source_tree.ancestors().skip(1) {
+        let path = directory.join("pyproject.toml");
+        let contents = match fs_err::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let pyproject_toml: toml::Table = toml::from_str(&contents)?;
+        let is_workspace_root = pyproject_toml
+            .get("tool")
+            .and_then(|tool| tool.get("uv"))
+            .and_then(|uv| uv.get("workspace"))
+            .is_some();
+        if is_workspace_root {
+            return Ok((directory.to_path_buf(), pyproject_toml));
+        }
+    }
+    Err(ValidationError::MissingWorkspaceRoot.into())
+}
+
+/// Build the `Description-Content-Type`, e.g., `text/markdown; charset=UTF-8; variant=GFM`.
+///
+/// The content type may already carry `charset` and `variant` parameters. The charset is always
+/// `UTF-8` and always included. For markdown, the variant defaults to
+/// `tool.uv.build-backend.markdown-variant`.
+///
+/// <https://packaging.python.org/en/latest/specifications/core-metadata/#description-content-type>
+fn description_content_type(
+    content_type: &str,
+    charset: Option<&str>,
+    settings: &BuildBackendSettings,
+) -> Result<String, ValidationError> {
+    let supported_content_types = ["text/plain", "text/x-rst", "text/markdown"];
+    let mut parts = content_type.split(';');
+    let media_type = parts.next().unwrap_or_default().trim();
+    if !supported_content_types.contains(&media_type) {
+        return Err(ValidationError::UnsupportedContentType(
+            content_type.to_string(),
+        ));
+    }
+
+    let mut charset = charset.map(ToString::to_string);
+    let mut variant = settings.markdown_variant.map(MarkdownVariant::as_str);
+    for parameter in parts {
+        let Some((key, value)) = parameter
+            .split_once('=')
+            .map(|(key, value)| (key.trim(), value.trim()))
+        else {
+            return Err(ValidationError::InvalidContentTypeParameter(
+                content_type.to_string(),
+            ));
+        };
+        let is_token = |token: &str| {
+            !token.is_empty()
+                && token
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_.+".contains(c))
+        };
+        if !is_token(key) || !is_token(value) {
+            return Err(ValidationError::InvalidContentTypeParameter(
+                content_type.to_string(),
+            ));
+        }
+        match key {
+            "charset" => charset = Some(value.to_string()),
+            "variant" if media_type == "text/markdown" => {
+                variant = Some(match value {
+                    "GFM" => MarkdownVariant::Gfm.as_str(),
+                    "CommonMark" => MarkdownVariant::CommonMark.as_str(),
+                    _ => return Err(ValidationError::MarkdownVariant(value.to_string())),
+                });
+            }
+            _ => {
+                return Err(ValidationError::UnknownContentTypeParameter(
+                    key.to_string(),
+                ))
+            }
+        }
+    }
+    if charset
+        .as_deref()
+        .is_some_and(|charset| !charset.eq_ignore_ascii_case("UTF-8"))
+    {
+        return Err(ValidationError::ReadmeCharset);
+    }
+
+    let mut content_type = format!("{media_type}; charset=UTF-8");
+    if media_type == "text/markdown" {
+        if let Some(variant) = variant {
+            content_type.push_str("; variant=");
+            content_type.push_str(variant);
+        }
+    }
+    Ok(content_type)
+}
+
+/// Read the readme file, which is relative to the directory containing the `pyproject.toml`, not
+/// to the current working directory.
+fn read_readme(root: &Path, readme: &Path) -> Result<String, Error> {
+    let path = root.join(readme);
+    match fs_err::read_to_string(&path) {
+        Ok(content) => Ok(content),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            Err(ValidationError::MissingReadme(path).into())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// The first line of the readme that isn't blank or decoration, without markdown heading markers,
+/// e.g., `Hello World` for `# Hello World`.
+///
+/// reStructuredText over- and underlines, such as `=====`, are skipped.
+fn readme_summary(readme: &str) -> Option<String> {
+    readme
+        .lines()
+        .map(str::trim)
+        .find(|line| {
+            !line.is_empty()
+                && !line
+                    .chars()
+                    .all(|c| c.is_ascii_punctuation() && line.starts_with(c))
+        })
+        .map(|line| line.trim_matches('#').trim().to_string())
+        .filter(|summary| !summary.is_empty())
+}
+
+/// Check that each value of a multiple-use core metadata field fits on a single line.
+fn check_single_line(field: &'static str, values: &[String]) -> Result<(), ValidationError> {
+    for value in values {
+        if value.contains('\n') || value.contains('\r') {
+            return Err(ValidationError::Newlines {
+                field,
+                value: value.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check that each entry is a distribution name with an optional version and marker, e.g.,
+/// `Foo (>=1.0) ; python_version < "3.12"`.
+///
+/// The version may be a plain version (`Provides-Dist`) or a version specifier
+/// (`Obsoletes-Dist`).
+fn check_distributions(field: &'static str, values: &[String]) -> Result<(), ValidationError> {
+    check_single_line(field, values)?;
+    for value in values {
+        let invalid = || ValidationError::InvalidDistribution {
+            field,
+            value: value.clone(),
+        };
+
+        let (distribution, marker) = match value.split_once(';') {
+            Some((distribution, marker)) => (distribution, Some(marker)),
+            None => (value.as_str(), None),
+        };
+        if let Some(marker) = marker {
+            MarkerTree::from_str(marker.trim()).map_err(|_| invalid())?;
+        }
+
+        let (name, version) = match distribution.split_once('(') {
+            Some((name, version)) => {
+                let version = version.trim_end().strip_suffix(')').ok_or_else(invalid)?;
+                (name, Some(version.trim()))
+            }
+            None => (distribution, None),
+        };
+        PackageName::from_str(name.trim()).map_err(|_| invalid())?;
+        if let Some(version) = version {
+            if Version::from_str(version).is_err() && VersionSpecifiers::from_str(version).is_err()
+            {
+                return Err(invalid());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Read a constraints file with one `<name><specifiers>` entry per line, e.g., `anyio<2.0`.
+///
+/// Empty lines and `#` comments are ignored. Multiple entries for the same package are combined.
+fn read_constraints(path: &Path) -> Result<BTreeMap<PackageName, VersionSpecifiers>, Error> {
+    let contents = fs_err::read_to_string(path)?;
+    let mut constraints: BTreeMap<PackageName, Vec<VersionSpecifier>> = BTreeMap::new();
+    for line in contents.lines() {
+        let line = line
+            .split_once('#')
+            .map_or(line, |(line, _comment)| line)
+            .trim();
+        if line.is_empty() {
+            continue;
+        }
+        let requirement = Requirement::<VerbatimParsedUrl>::from_str(line)
+            .map_err(|_| ValidationError::InvalidConstraint(line.to_string()))?;
+        let Some(VersionOrUrl::VersionSpecifier(specifiers)) = requirement.version_or_url else {
+            return Err(ValidationError::InvalidConstraint(line.to_string()).into());
+        };
+        constraints
+            .entry(requirement.name)
+            .or_default()
+            .extend(specifiers.iter().cloned());
+    }
+    Ok(constraints
+        .into_iter()
+        .map(|(name, specifiers)| (name, specifiers.into_iter().collect()))
+        .collect())
+}
+
+/// Narrow the version specifiers of the requirements to the constraints.
+///
+/// Requirements with a URL are left unchanged. If the project's specifiers and the constraints
+/// don't have a version in common, the build fails rather than emitting an unsatisfiable
+/// requirement.
+fn apply_constraints(
+    requirements: &mut [Requirement<VerbatimParsedUrl>],
+    constraints: &BTreeMap<PackageName, VersionSpecifiers>,
+) -> Result<(), ValidationError> {
+    for requirement in requirements {
+        let Some(constraint) = constraints.get(&requirement.name) else {
+            continue;
+        };
+        let specifiers = match &requirement.version_or_url {
+            None => constraint.clone(),
+            Some(VersionOrUrl::VersionSpecifier(specifiers)) => specifiers
+                .iter()
+                .chain(constraint.iter())
+                .unique()
+                .cloned()
+                .collect(),
+            Some(VersionOrUrl::Url(_)) => continue,
+        };
+        let is_empty = PubGrubSpecifier::from_pep440_specifiers(&specifiers)
+            .is_ok_and(|range| range.iter().next().is_none());
+        if is_empty {
+            return Err(ValidationError::ConflictingConstraint {
+                name: requirement.name.clone(),
+                specifiers,
+            });
+        }
+        debug!("Constraining `{}` to `{specifiers}`", requirement.name);
+        requirement.version_or_url = Some(VersionOrUrl::VersionSpecifier(specifiers));
+    }
+    Ok(())
+}
+
+/// Whether the string is a valid Python identifier, e.g., a module or attribute name.
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Whether the string follows the recommended entry point name format, `[\w.-]+`, e.g.,
+/// `my-tool`.
+fn is_entry_point_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Whether the string is a valid entry point group name, e.g., `pytest11` or
+/// `myframework.plugins`.
+fn is_entry_point_group(group: &str) -> bool {
+    group.split('.').all(|component| {
+        !component.is_empty() && component.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// The fields in the order of the core metadata specification.
+///
+/// <https://packaging.python.org/en/latest/specifications/core-metadata/>
+const SPECIFICATION_FIELD_ORDER: &[&str] = &[
+    "Metadata-Version",
+    "Name",
+    "Version",
+    "Dynamic",
+    "Platform",
+    "Supported-Platform",
+    "Summary",
+    "Description",
+    "Description-Content-Type",
+    "Keywords",
+    "Home-Page",
+    "Download-URL",
+    "Author",
+    "Author-email",
+    "Maintainer",
+    "Maintainer-email",
+    "License",
+    "License-Expression",
+    "License-File",
+    "Classifier",
+    "Requires-Dist",
+    "Requires-Python",
+    "Requires-External",
+    "Project-URL",
+    "Provides-Extra",
+    "Provides-Dist",
+    "Obsoletes-Dist",
+];
+
+/// Reorder the headers of formatted core metadata to [`SPECIFICATION_FIELD_ORDER`].
+///
+/// Repeated fields keep their order and the description stays at the end. With grouped extras,
+/// each `Provides-Extra` stays in front of its `Requires-Dist` entries.
+fn specification_field_order(formatted: &str, group_extras: bool) -> String {
+    let (headers, description) = match formatted.split_once("\n\n") {
+        Some((headers, description)) => (headers, Some(description)),
+        None => (formatted.trim_end_matches('\n'), None),
+    };
+
+    // Multi-line values are continued on indented lines.
+    let mut fields: Vec<Vec<&str>> = Vec::new();
+    for line in headers.lines() {
+        match fields.last_mut() {
+            Some(field) if line.starts_with(' ') => field.push(line),
+            _ => fields.push(vec![line]),
+        }
+    }
+    fields.sort_by_key(|field| {
+        let key = field[0].split_once(':').map_or(field[0], |(key, _)| key);
+        let key = if group_extras && key.eq_ignore_ascii_case("Provides-Extra") {
+            "Requires-Dist"
+        } else {
+            key
+        };
+        SPECIFICATION_FIELD_ORDER
+            .iter()
+            .position(|field| field.eq_ignore_ascii_case(key))
+            .unwrap_or(SPECIFICATION_FIELD_ORDER.len())
+    });
+
+    let mut reordered = fields.concat().join("\n");
+    reordered.push('\n');
+    if let Some(description) = description {
+        reordered.push('\n');
+        reordered.push_str(description);
+    }
+    reordered
+}
+
+/// The core metadata versions that can be set with `tool.uv.build-backend.metadata-version`.
+const METADATA_VERSIONS: &[&str] = &["1.0", "1.1", "1.2", "2.1", "2.2", "2.3", "2.4"];
+
+/// Check that the metadata only uses fields that exist in the pinned metadata version.
+///
+/// <https://packaging.python.org/en/latest/specifications/core-metadata/>
+fn check_metadata_version(
+    metadata: &Metadata23,
+    metadata_version: &str,
+) -> Result<(), ValidationError> {
+    if !METADATA_VERSIONS.contains(&metadata_version) {
+        return Err(ValidationError::UnknownMetadataVersion(
+            metadata_version.to_string(),
+        ));
+    }
+    let version = Version::from_str(metadata_version)
+        .map_err(|_| ValidationError::UnknownMetadataVersion(metadata_version.to_string()))?;
+
+    if let Some((field, required)) = required_metadata_version(metadata) {
+        if version < Version::from_str(required).expect("valid version") {
+            return Err(ValidationError::MetadataVersionTooLow {
+                field,
+                required,
+                version: metadata_version.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The highest metadata version required by the fields in use, with the field that requires
+/// it, or `None` if the metadata only uses fields from 1.0.
+fn required_metadata_version(metadata: &Metadata23) -> Option<(&'static str, &'static str)> {
+    // The fields that were added after 1.0, with the version that added them.
+    let fields = [
+        (
+            "Supported-Platform",
+            "1.1",
+            !metadata.supported_platforms.is_empty(),
+        ),
+        ("Download-URL", "1.1", metadata.download_url.is_some()),
+        ("Classifier", "1.1", !metadata.classifiers.is_empty()),
+        ("Maintainer", "1.2", metadata.maintainer.is_some()),
+        (
+            "Maintainer-email",
+            "1.2",
+            metadata.maintainer_email.is_some(),
+        ),
+        ("Requires-Dist", "1.2", !metadata.requires_dist.is_empty()),
+        ("Requires-Python", "1.2", metadata.requires_python.is_some()),
+        (
+            "Requires-External",
+            "1.2",
+            !metadata.requires_external.is_empty(),
+        ),
+        ("Project-URL", "1.2", !metadata.project_urls.is_empty()),
+        ("Provides-Dist", "1.2", !metadata.provides_dist.is_empty()),
+        ("Obsoletes-Dist", "1.2", !metadata.obsoletes_dist.is_empty()),
+        (
+            "Description-Content-Type",
+            "2.1",
+            metadata.description_content_type.is_some(),
+        ),
+        (
+            "Provides-Extra",
+            "2.1",
+            !metadata.provides_extras.is_empty(),
+        ),
+        ("Dynamic", "2.2", !metadata.dynamic.is_empty()),
+        (
+            "License-Expression",
+            "2.4",
+            metadata.license_expression.is_some(),
+        ),
+        ("License-File", "2.4", !metadata.license_files.is_empty()),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, _, used)| *used)
+        .map(|(field, required, _)| (field, required))
+        .max_by_key(|(_, required)| Version::from_str(required).expect("valid version"))
+}
+
+/// The Python version of a `Programming Language :: Python :: 3.8` style classifier.
+///
+/// Classifiers without a minor version, such as `Programming Language :: Python :: 3` or
+/// `Programming Language :: Python :: 3 :: Only`, are too coarse to derive a bound from.
+fn python_classifier_version(classifier: &str) -> Option<Version> {
+    let version = classifier.strip_prefix("Programming Language :: Python :: ")?;
+    let version = Version::from_str(version.trim()).ok()?;
+    (version.release().len() >= 2).then_some(version)
+}
+
+/// The `[project]` section of a pyproject.toml as specified in
+/// <https://packaging.python.org/en/latest/specifications/pyproject-toml>.
+///
+/// This struct does not have schema export; the schema is shared between all Python tools, and we
+/// should update the shared schema instead.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+struct Project {
+    /// The name of the project.
+    name: PackageName,
+    /// The version of the project.
+    version: Version,
+    /// The summary description of the project in one line.
+    description: Option<String>,
+    /// The full description of the project (i.e. the README).
+    readme: Option<Readme>,
+    /// The Python version requirements of the project.
+    ///
+    /// Parsed when building the metadata, to report invalid specifiers with the field name.
+    requires_python: Option<String>,
+    /// The license under which the project is distributed.
+    ///
+    /// Supports both the current standard and the provisional PEP 639.
+    license: Option<License>,
+    /// The paths to files containing licenses and other legal notices to be distributed with the
+    /// project.
+    ///
+    /// From the provisional PEP 639
+    license_files: Option<Vec<String>>,
+    /// The people or organizations considered to be the "authors" of the project.
+    authors: Option<Vec<Contact>>,
+    /// The people or organizations considered to be the "maintainers" of the project.
+    maintainers: Option<Vec<Contact>>,
+    /// The keywords for the project.
+    keywords: Option<Vec<String>>,
+    /// Trove classifiers which apply to the project.
+    classifiers: Option<Vec<String>>,
+    /// A table of URLs where the key is the URL label and the value is the URL itself.
+    ///
+    /// PyPI shows all URLs with their name. For some known patterns, they add favicons.
+    /// main: <https://github.com/pypi/warehouse/blob/main/warehouse/templates/packaging/detail.html>
+    /// archived: <https://github.com/pypi/warehouse/blob/e3bd3c3805ff47fff32b67a899c1ce11c16f3c31/warehouse/templates/packaging/detail.html>
+    urls: Option<BTreeMap<String, String>>,
+    /// The console entrypoints of the project.
+    ///
+    /// The key of the table is the name of the entry point and the value is the object reference.
+    scripts: Option<BTreeMap<String, String>>,
+    /// The GUI entrypoints of the project, which don't open a console window on Windows.
+    ///
+    /// The key of the table is the name of the entry point and the value is the object reference.
+    gui_scripts: Option<BTreeMap<String, String>>,
+    /// The entry points of the project, by group, e.g., plugins for other packages.
+    ///
+    /// The key of the inner table is the name of the entry point and the value is the object
+    /// reference.
+    entry_points: Option<BTreeMap<String, BTreeMap<String, String>>>,
+    /// The dependencies of the project.
+    dependencies: Option<Vec<Requirement<VerbatimParsedUrl>>>,
+    /// The optional dependencies of the project.
+    optional_dependencies: Option<BTreeMap<ExtraName, Vec<Requirement<VerbatimParsedUrl>>>>,
+}
+
+/// The optional `project.readme` key in a pyproject.toml as specified in
+/// <https://packaging.python.org/en/latest/specifications/pyproject-toml/#readme>.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged, rename_all = "kebab-case")]
+enum Readme {
+    /// Relative path to the README.
+    String(PathBuf),
+    /// Relative path to the README.
+    File {
+        file: PathBuf,
+        content_type: String,
+        charset: Option<String>,
+    },
+    /// The full description of the project as inline value.
+    Text {
+        text: String,
+        content_type: String,
+        charset: Option<String>,
+    },
+}
+
+/// The optional `project.license` key in a pyproject.toml as specified in
+/// <https://packaging.python.org/en/latest/specifications/pyproject-toml/#license>.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum License {
+    /// An SPDX Expression.
+    ///
+    /// From the provisional PEP 639.
+    Spdx(String),
+    Text {
+        /// The full text of the license.
+        text: String,
+    },
+    File {
+        /// The file containing the license text.
+        file: PathBuf,
+    },
+}
+
+/// A `project.authors` or `project.maintainers` entry as specified in
+/// <https://packaging.python.org/en/latest/specifications/pyproject-toml/#authors-maintainers>.
+///
+/// The entry is derived from the email format of `John Doe <john.doe@example.net>`. You need to
+/// provide at least name or email.
+#[derive(Deserialize, Debug, Clone)]
+// deny_unknown_fields prevents using the name field when the email is not a string.
+#[serde(
+    untagged,
+    deny_unknown_fields,
+    expecting = "a table with 'name' and/or 'email' keys"
+)]
+enum Contact {
+    /// TODO(konsti): RFC 822 validation.
+    NameEmail { name: String, email: String },
+    /// TODO(konsti): RFC 822 validation.
+    Name { name: String },
+    /// TODO(konsti): RFC 822 validation.
+    Email { email: String },
+}
+
+impl Contact {
+    /// Split the contacts into the name-only field (`Author` or `Maintainer`) and the email field
+    /// (`Author-email` or `Maintainer-email`).
+    ///
+    /// Contacts with only a name go to the name field, contacts with an email go to the email
+    /// field, as specified in
+    /// <https://packaging.python.org/en/latest/specifications/pyproject-toml/#authors-maintainers>.
+    fn to_fields(contacts: Option<&[Contact]>) -> (Option<String>, Option<String>) {
+        let contacts = contacts.unwrap_or_default();
+        let names = contacts
+            .iter()
+            .filter_map(|contact| match contact {
+                Contact::Name { name } => Some(name.as_str()),
+                Contact::NameEmail { .. } | Contact::Email { .. } => None,
+            })
+            .join(", ");
+        let emails = contacts
+            .iter()
+            .filter(|contact| !matches!(contact, Contact::Name { .. }))
+            .map(Contact::to_email_format)
+            .join(", ");
+        (
+            Some(names).filter(|names| !names.is_empty()),
+            Some(emails).filter(|emails| !emails.is_empty()),
+        )
+    }
+
+    /// Format the contact in the RFC 822 style of `Author-email` and `Maintainer-email`.
+    fn to_email_format(&self) -> String {
+        match self {
+            Contact::NameEmail { name, email } => format!("{name} <{email}>"),
+            Contact::Name { name } => name.clone(),
+            Contact::Email { email } => email.clone(),
+        }
+    }
+}
+
+/// The `[build-system]` section of a pyproject.toml as specified in PEP 517.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+struct BuildSystem {
+    /// The requirements needed to run the build backend.
+    #[serde(default)]
+    requires: Vec<Requirement<VerbatimParsedUrl>>,
+    /// A string naming a Python object that will be used to perform the build.
+    build_backend: Option<String>,
+}
+
+/// The `[tool]` section of a pyproject.toml.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+struct Tool {
+    #[serde(default)]
+    uv: ToolUv,
+}
+
+/// The `[tool.uv]` section of a pyproject.toml, we only read the build backend settings.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+struct ToolUv {
+    #[serde(default)]
+    build_backend: BuildBackendSettings,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::{formatdoc, indoc};
+    use insta::assert_snapshot;
+    use std::iter;
+
+    fn format_err(err: impl std::error::Error) -> String {
+        let mut formatted = err.to_string();
+        for source in iter::successors(err.source(), |&err| err.source()) {
+            formatted += &format!("\n  Caused by: {source}");
+        }
+        formatted
+    }
+
+    #[test]
+    fn supported_platforms() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            supported-platforms = ["RedHat 7.2", "i386-win32-2791"]
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Supported-Platform: RedHat 7.2
+        Supported-Platform: i386-win32-2791
+        "###);
+    }
+
+    #[test]
+    fn platforms() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            platforms = ["linux", "ObscureUnix"]
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Platform: linux
+        Platform: ObscureUnix
+        "###);
+    }
+
+    #[test]
+    fn requires_external() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            requires-external = ["C", "libxml2 (>=2.9)"]
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Requires-External: C
+        Requires-External: libxml2 (>=2.9)
+        "###);
+    }
+
+    #[test]
+    fn provides_and_obsoletes_dist() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            provides-dist = ["hello", "world (1.0)"]
+            obsoletes-dist = ["hello-old (<0.1)", "hello-win32 ; sys_platform == 'win32'"]
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Provides-Dist: hello
+        Provides-Dist: world (1.0)
+        Obsoletes-Dist: hello-old (<0.1)
+        Obsoletes-Dist: hello-win32 ; sys_platform == 'win32'
+        "###);
+    }
+
+    #[test]
+    fn invalid_provides_dist() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            provides-dist = ["hello (1.0"]
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let err = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap_err();
+
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: Entries in `tool.uv.build-backend.provides-dist` must be a distribution name, optionally followed by a version in parentheses and a marker, invalid entry: `hello (1.0`
+        "###);
+    }
+
+    /// Parse a `hello-world` project reading its version from `src/hello_world/__init__.py`.
+    fn attr_version_project(init_py: &str) -> (tempfile::TempDir, Result<PyProjectToml, Error>) {
+        let project = tempfile::TempDir::new().unwrap();
+        let module = project.path().join("src").join("hello_world");
+        fs_err::create_dir_all(&module).unwrap();
+        fs_err::write(module.join("__init__.py"), init_py).unwrap();
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            dynamic = ["version"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            version-source = "attr:hello_world.__version__"
+        "#};
+        let pyproject_toml = PyProjectToml::parse_with_workspace(contents, project.path());
+        (project, pyproject_toml)
+    }
+
+    #[test]
+    fn attr_version() {
+        let (_project, pyproject_toml) = attr_version_project(indoc! {r#"
+            """Hello world."""
+
+            __version_info__ = (1, 2, 3)
+            __version__ = "1.2.3"  # Updated by the release script.
+        "#});
+        assert_eq!(pyproject_toml.unwrap().version().to_string(), "1.2.3");
+    }
+
+    #[test]
+    fn attr_version_computed() {
+        let (project, pyproject_toml) = attr_version_project(indoc! {r#"
+            __version_info__ = (1, 2, 3)
+            __version__ = ".".join(map(str, __version_info__))
+        "#});
+        let err = format_err(pyproject_toml.unwrap_err())
+            .replace(&project.path().simplified_display().to_string(), "[TEMP]")
+            .replace('\\', "/");
+        assert_snapshot!(err, @r###"
+        Invalid pyproject.toml
+          Caused by: `hello_world.__version__` in `[TEMP]/src/hello_world/__init__.py` must be assigned a plain string literal, computed versions are not supported
+        "###);
+    }
+
+    #[test]
+    fn attr_version_module() {
+        let project = tempfile::TempDir::new().unwrap();
+        let module = project.path().join("src").join("hello_world");
+        fs_err::create_dir_all(&module).unwrap();
+        fs_err::write(module.join("__init__.py"), "").unwrap();
+        fs_err::write(module.join("_version.py"), "__version__ = '2.0.0'\n").unwrap();
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            dynamic = ["version"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            version-source = "attr:hello_world._version.__version__"
+        "#};
+        let pyproject_toml = PyProjectToml::parse_with_workspace(contents, project.path()).unwrap();
+        assert_eq!(pyproject_toml.version().to_string(), "2.0.0");
+    }
+
+    #[test]
+    fn file_version() {
+        let project = tempfile::TempDir::new().unwrap();
+        fs_err::write(project.path().join("VERSION"), "1.2.3\n").unwrap();
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            dynamic = ["version"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            version-source = "file:VERSION"
+        "#};
+        let pyproject_toml = PyProjectToml::parse_with_workspace(contents, project.path()).unwrap();
+        assert_eq!(pyproject_toml.version().to_string(), "1.2.3");
+
+        fs_err::write(project.path().join("VERSION"), "latest\n").unwrap();
+        let err = PyProjectToml::parse_with_workspace(contents, project.path()).unwrap_err();
+        let err = format_err(err)
+            .replace(&project.path().simplified_display().to_string(), "[TEMP]")
+            .replace('\\', "/");
+        assert_snapshot!(err, @r###"
+        Invalid pyproject.toml
+          Caused by: `[TEMP]/VERSION` does not contain a valid version
+          Caused by: expected version to start with a number, but no leading ASCII digits were found
+        "###);
+    }
+
+    #[test]
+    fn dynamic_version_without_source() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            dynamic = ["version"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let err =
+            PyProjectToml::parse_with_workspace(contents, Path::new("/do/not/read")).unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: `project.dynamic` contains `version`, but `tool.uv.build-backend.version-source` is not set, e.g., to `attr:<module>.__version__`
+        "###);
+    }
+
+    #[test]
+    fn dynamic_contradictory() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            dynamic = ["version"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let err =
+            PyProjectToml::parse_with_workspace(contents, Path::new("/do/not/read")).unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: `project.version` is listed in `project.dynamic`, but also set statically
+        "###);
+
+        // A version read from a source must be declared as dynamic.
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            version-source = "file:VERSION"
+        "#};
+        let err =
+            PyProjectToml::parse_with_workspace(contents, Path::new("/do/not/read")).unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: `project.version` is read from `tool.uv.build-backend.version-source`, but it is not listed in `project.dynamic`
+        "###);
+    }
+
+    #[test]
+    fn dynamic_without_provider() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            dynamic = ["dependencies"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let err =
+            PyProjectToml::parse_with_workspace(contents, Path::new("/do/not/read")).unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: `project.dependencies` is listed in `project.dynamic`, but the uv build backend can't provide it
+        "###);
+    }
+
+    #[test]
+    fn inherit_from_workspace() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        fs_err::write(
+            workspace.path().join("pyproject.toml"),
+            indoc! {r#"
+                [project]
+                name = "workspace"
+                version = "1.0.0"
+                authors = [{ name = "Ferris the crab" }]
+                keywords = ["shared"]
+
+                [tool.uv.workspace]
+                members = ["packages/*"]
+            "#},
+        )
+        .unwrap();
+        let member = workspace.path().join("packages").join("hello-world");
+        fs_err::create_dir_all(&member).unwrap();
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            keywords = ["member"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            inherit = ["version", "authors", "keywords"]
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse_with_workspace(contents, &member).unwrap();
+        let metadata = pyproject_toml.to_metadata(&member).unwrap();
+
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 1.0.0
+        Keywords: member
+        Author: Ferris the crab
+        "###);
+    }
+
+    #[test]
+    fn inherit_missing_version() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        fs_err::write(
+            workspace.path().join("pyproject.toml"),
+            indoc! {r#"
+                [project]
+                name = "workspace"
+                version = "1.0.0"
+                authors = [{ name = "Ferris the crab" }]
+
+                [tool.uv.workspace]
+                members = ["packages/*"]
+            "#},
+        )
+        .unwrap();
+        let member = workspace.path().join("packages").join("hello-world");
+        fs_err::create_dir_all(&member).unwrap();
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            inherit = ["authors"]
+        "#};
+
+        let err = PyProjectToml::parse_with_workspace(contents, &member).unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: `project.version` is required, but it is neither set in the project nor inherited from the workspace root
+        "###);
+    }
+
+    #[test]
+    fn strict_build_system() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["setuptools"]
+            build-backend = "setuptools.build_meta"
+
+            [tool.uv.build-backend]
+            strict = true
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let err = pyproject_toml.check_build_system().unwrap_err();
+        assert_snapshot!(err.to_string(), @r###"The value for `build_system.build-backend` should be `"uv"`, not `"setuptools.build_meta"` (warnings are errors because `tool.uv.build-backend.strict` is set)"###);
+
+        // Without strict mode, this is only a warning.
+        let contents = contents.replace("strict = true", "strict = false");
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        pyproject_toml.check_build_system().unwrap();
+    }
+
+    #[test]
+    fn build_system_requires() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["hatchling"]
+            build-backend = "hatchling.build"
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        pyproject_toml.check_build_system().unwrap();
+        assert_eq!(
+            pyproject_toml.warnings(),
+            [
+                r#"The value for `build_system.build-backend` should be `"uv"`, not `"hatchling.build"`"#,
+                "`build_system.requires` should contain `uv`, but it only contains: `hatchling`"
+            ]
+        );
+
+        // A uv requirement that excludes the running version.
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<0.5"]
+            build-backend = "uv"
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        pyproject_toml
+            .check_build_system_requires(&Version::from_str("0.5.0").unwrap())
+            .unwrap();
+        assert_eq!(
+            pyproject_toml.warnings(),
+            ["`build_system.requires` contains `uv>=0.4.15, <0.5`, which does not include the running version of uv (0.5.0)"]
+        );
+        pyproject_toml
+            .check_build_system_requires(&Version::from_str("0.4.17").unwrap())
+            .unwrap();
+        assert_eq!(pyproject_toml.warnings().len(), 1);
+    }
+
+    #[test]
+    fn supported_platforms_multiline() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            supported-platforms = ["RedHat 7.2\nRedHat 8"]
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let err = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: Entries in `tool.uv.build-backend.supported-platforms` must be a single line, invalid entry: `RedHat 7.2
+        RedHat 8`
+        "###);
+    }
+
+    #[test]
+    fn infer_requires_python() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            classifiers = [
+                "Programming Language :: Python :: 3",
+                "Programming Language :: Python :: 3 :: Only",
+                "Programming Language :: Python :: 3.10",
+                "Programming Language :: Python :: 3.8",
+                "Programming Language :: Python :: 3.9",
+            ]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            infer-requires-python = true
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_eq!(metadata.requires_python.as_deref(), Some(">=3.8"));
+
+        // An explicit `requires-python` is never overridden.
+        let contents = contents.replace(
+            "version = \"0.1.0\"\n",
+            "version = \"0.1.0\"\nrequires-python = \">=3.12\"\n",
+        );
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_eq!(metadata.requires_python.as_deref(), Some(">=3.12"));
+
+        // Without the setting, nothing is inferred.
+        let contents = contents
+            .replace("requires-python = \">=3.12\"\n", "")
+            .replace("infer-requires-python = true\n", "");
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_eq!(metadata.requires_python, None);
+    }
+
+    #[test]
+    fn requires_python() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            requires-python = ">=3.8,<4"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Requires-Python: >=3.8, <4
+        "###);
+
+        let contents = contents.replace(">=3.8,<4", ">=3.8,<=");
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let err = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap_err();
+        let Error::Validation(err) = err else {
+            panic!("{err}");
+        };
+        assert_eq!(
+            err.to_string(),
+            "`project.requires-python` is not a valid PEP 440 version specifier: `>=3.8,<=`"
+        );
+    }
+
+    #[test]
+    fn legacy_urls() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [project.urls]
+            Homepage = "https://example.org"
+            Download = "https://example.org/download"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            legacy-urls = true
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Home-Page: https://example.org
+        Download-URL: https://example.org/download
+        Project-URL: Download, https://example.org/download
+        Project-URL: Homepage, https://example.org
+        "###);
+    }
+
+    #[test]
+    fn invalid_legacy_url() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [project.urls]
+            Homepage = "example.org"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            legacy-urls = true
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let err = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: `project.urls.Homepage` must be a URL, invalid value: `example.org`
+          Caused by: relative URL without a base
+        "###);
+    }
+
+    #[test]
+    fn constraints() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            dependencies = ["anyio>=1.0", "idna", "sniffio"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            constraints = "constraints.txt"
+        "#};
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs_err::write(
+            temp_dir.path().join("constraints.txt"),
+            indoc! {"
+                # Locked down for the release.
+                anyio<2.0
+                idna>=3.0,<4.0
+            "},
+        )
+        .unwrap();
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml.to_metadata(temp_dir.path()).unwrap();
+
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Requires-Dist: anyio>=1.0, <2.0
+        Requires-Dist: idna>=3.0, <4.0
+        Requires-Dist: sniffio
+        "###);
+    }
+
+    #[test]
+    fn conflicting_constraints() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            dependencies = ["anyio>=2.0"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            constraints = "constraints.txt"
+        "#};
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs_err::write(temp_dir.path().join("constraints.txt"), "anyio<2.0\n").unwrap();
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let err = pyproject_toml.to_metadata(temp_dir.path()).unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: The constraints for `anyio` exclude all versions allowed by the project: `>=2.0, <2.0`
+        "###);
+    }
+
+    #[test]
+    fn multiline_description() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            description = """
+            A greeting.
+            Now with more lines.
+            """
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let err = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: Entries in `project.description` must be a single line, invalid entry: `A greeting.
+        Now with more lines.
+        `
+        "###);
+    }
+
+    #[test]
+    fn long_description_strict() {
+        let contents = formatdoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            description = "{}"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            strict = true
+        "#, "a".repeat(SUMMARY_LENGTH + 1)};
+
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let err = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: `project.description` is longer than 512 characters, indexes may reject it (warnings are errors because `tool.uv.build-backend.strict` is set)
+        "###);
+    }
+
+    #[test]
+    fn license_files_case_sensitivity() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            license-files = ["LICENSE*"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs_err::write(temp_dir.path().join("LICENSE.txt"), "").unwrap();
+        fs_err::write(temp_dir.path().join("license-mit.txt"), "").unwrap();
+
+        // Case-sensitive by default.
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml.to_metadata(temp_dir.path()).unwrap();
+        assert_eq!(metadata.license_files, ["LICENSE.txt"]);
+
+        let contents = formatdoc! {r#"
+            {contents}
+            [tool.uv.build-backend.glob-options]
+            case-sensitive = false
+        "#};
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let metadata = pyproject_toml.to_metadata(temp_dir.path()).unwrap();
+        assert_eq!(metadata.license_files, ["LICENSE.txt", "license-mit.txt"]);
+    }
+
+    #[test]
+    fn dependency_with_multiple_markers() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            dependencies = [
+                "foo>=2; python_version >= '3.9'",
+                "bar",
+                "foo<2; python_version < '3.9'",
+            ]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Requires-Dist: foo>=2 ; python_full_version >= '3.9'
+        Requires-Dist: bar
+        Requires-Dist: foo<2 ; python_full_version < '3.9'
+        "###);
+    }
+
+    #[test]
+    fn optional_dependencies() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            dependencies = ["anyio>=4", "colorama; sys_platform == 'win32'"]
+
+            [project.optional-dependencies]
+            cli = ["click>=8", "pywin32; sys_platform == 'win32'"]
+            test = ["pytest"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+
+        // The markers of optional dependencies are combined with the extra marker.
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Requires-Dist: anyio>=4
+        Requires-Dist: colorama ; sys_platform == 'win32'
+        Requires-Dist: click>=8 ; extra == 'cli'
+        Requires-Dist: pywin32 ; sys_platform == 'win32' and extra == 'cli'
+        Requires-Dist: pytest ; extra == 'test'
+        Provides-Extra: cli
+        Provides-Extra: test
+        "###);
+    }
+
+    #[test]
+    fn legacy_license_table_warning() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            license = { text = "MIT" }
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_eq!(metadata.license.as_deref(), Some("MIT"));
+        assert_eq!(
+            pyproject_toml.warnings(),
+            [
+                r#"`project.license = { text = "..." }` is deprecated, use an SPDX expression such as `license = "MIT"` instead"#
+            ]
+        );
+
+        // The SPDX expression form is not deprecated.
+        let contents = contents.replace("license = { text = \"MIT\" }", "license = \"MIT\"");
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_eq!(metadata.license_expression.as_deref(), Some("MIT"));
+        assert!(pyproject_toml.warnings().is_empty());
+    }
+
+    #[test]
+    fn metadata_field_order() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            description = "A greeting"
+            readme = { text = "Hello world!", content-type = "text/plain" }
+            requires-python = ">=3.9"
+            license = "MIT"
+            maintainers = [{ name = "Ferris" }]
+            classifiers = ["Private :: Do Not Upload"]
+            dependencies = ["anyio"]
+            urls = { Homepage = "https://example.org" }
+
+            [project.optional-dependencies]
+            cli = ["click"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            provides-dist = ["hello"]
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        let fields = |formatted: &str| -> Vec<String> {
+            formatted
+                .lines()
+                .take_while(|line| !line.is_empty())
+                .filter_map(|line| line.split_once(": ").map(|(key, _)| key.to_string()))
+                .dedup()
+                .collect()
+        };
+        assert_eq!(
+            fields(&pyproject_toml.format_metadata(&metadata)),
+            [
+                "Metadata-Version",
+                "Name",
+                "Version",
+                "Summary",
+                "Description-Content-Type",
+                "Maintainer",
+                "License-Expression",
+                "Classifier",
+                "Requires-Dist",
+                "Requires-Python",
+                "Project-URL",
+                "Provides-Extra",
+                "Provides-Dist",
+            ]
+        );
+        // Only the headers are reordered.
+        assert!(pyproject_toml
+            .format_metadata(&metadata)
+            .ends_with("Provides-Dist: hello\n\nHello world!"));
+        assert_eq!(
+            Metadata23::parse(pyproject_toml.format_metadata(&metadata).as_bytes()).unwrap(),
+            Metadata23::parse(metadata.core_metadata_format().as_bytes()).unwrap()
+        );
+
+        let contents = format!("{contents}metadata-field-order = \"legacy\"\n");
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        assert_eq!(
+            pyproject_toml.format_metadata(&metadata),
+            metadata.core_metadata_format()
+        );
+    }
+
+    #[test]
+    fn pinned_metadata_version() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            dependencies = ["anyio"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            metadata-version = "2.1"
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.1
+        Name: hello-world
+        Version: 0.1.0
+        Requires-Dist: anyio
+        "###);
+
+        // PEP 639 fields require 2.4.
+        let contents = contents.replace(
+            "version = \"0.1.0\"\n",
+            "version = \"0.1.0\"\nlicense = \"MIT\"\n",
+        );
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let err = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: `License-Expression` requires `Metadata-Version: 2.4` or later, but `tool.uv.build-backend.metadata-version` is `2.1`
+        "###);
+    }
+
+    #[test]
+    fn automatic_metadata_version() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            license = "MIT"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+
+        // PEP 639 fields select 2.4.
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_snapshot!(metadata.core_metadata_format(), @r###"
+        Metadata-Version: 2.4
+        Name: hello-world
+        Version: 0.1.0
+        License-Expression: MIT
+        "###);
+
+        // Without them, the default stays 2.3 and lower versions can still be pinned.
+        let contents = contents.replace("license = \"MIT\"\n", "dependencies = [\"anyio\"]\n");
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_eq!(metadata.metadata_version, "2.3");
+        for pinned in ["2.1", "2.2"] {
+            let contents =
+                format!("{contents}\n[tool.uv.build-backend]\nmetadata-version = \"{pinned}\"\n");
+            let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+            let metadata = pyproject_toml
+                .to_metadata(Path::new("/do/not/read"))
+                .unwrap();
+            assert_eq!(metadata.metadata_version, pinned);
+        }
+    }
+
+    /// A project with a plugin in the `myframework.plugins` group whose module is generated at
+    /// runtime.
+    const DYNAMIC_PLUGIN: &str = indoc! {r#"
+        [project]
+        name = "hello-world"
+        version = "0.1.0"
+
+        [project.scripts]
+        hello = "hello_world.cli:main"
+
+        [project.entry-points."myframework.plugins"]
+        hello = "hello_world.generated:plugin"
+
+        [build-system]
+        requires = ["uv>=0.4.15,<5"]
+        build-backend = "uv"
+    "#};
+
+    #[test]
+    fn entry_point_missing_module() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs_err::create_dir_all(temp_dir.path().join("src/hello_world")).unwrap();
+        fs_err::write(temp_dir.path().join("src/hello_world/__init__.py"), "").unwrap();
+        fs_err::write(temp_dir.path().join("src/hello_world/cli.py"), "").unwrap();
+
+        let pyproject_toml = PyProjectToml::parse(DYNAMIC_PLUGIN).unwrap();
+        let err = pyproject_toml
+            .to_entry_points(temp_dir.path(), &EntryPoints::new())
+            .unwrap_err();
+        assert_snapshot!(err, @"The module of entry point `hello` in `myframework.plugins` doesn't exist: `hello_world.generated`");
+    }
+
+    #[test]
+    fn dynamic_entry_point_group() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs_err::create_dir_all(temp_dir.path().join("src/hello_world")).unwrap();
+        fs_err::write(temp_dir.path().join("src/hello_world/__init__.py"), "").unwrap();
+        fs_err::write(temp_dir.path().join("src/hello_world/cli.py"), "").unwrap();
+
+        let contents = formatdoc! {r#"
+            {DYNAMIC_PLUGIN}
+            [tool.uv.build-backend]
+            dynamic-entry-point-groups = ["myframework.plugins"]
+        "#};
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let entry_points = pyproject_toml
+            .to_entry_points(temp_dir.path(), &EntryPoints::new())
+            .unwrap()
+            .unwrap();
+        assert_snapshot!(entry_points, @r###"
+        [console_scripts]
+        hello = hello_world.cli:main
+
+        [myframework.plugins]
+        hello = hello_world.generated:plugin
+        "###);
+
+        // The exempt groups must be valid group names.
+        let contents = formatdoc! {r#"
+            {DYNAMIC_PLUGIN}
+            [tool.uv.build-backend]
+            dynamic-entry-point-groups = ["my framework"]
+        "#};
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let err = pyproject_toml
+            .to_entry_points(temp_dir.path(), &EntryPoints::new())
+            .unwrap_err();
+        assert_snapshot!(err, @"Invalid entry point group name, expected letters, numbers and underscores separated by dots: `my framework`");
+    }
+
+    #[test]
+    fn entry_point_groups() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs_err::create_dir_all(temp_dir.path().join("src/hello_world")).unwrap();
+        for module in ["__init__.py", "cli.py", "gui.py", "plugin.py"] {
+            fs_err::write(temp_dir.path().join("src/hello_world").join(module), "").unwrap();
+        }
+
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [project.scripts]
+            hello = "hello_world.cli:main"
+
+            [project.gui-scripts]
+            hello-gui = "hello_world.gui:main"
+
+            [project.entry-points.pytest11]
+            hello = "hello_world.plugin"
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+        "#};
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let entry_points = pyproject_toml
+            .to_entry_points(temp_dir.path(), &EntryPoints::new())
+            .unwrap()
+            .unwrap();
+        assert_snapshot!(entry_points, @r###"
+        [console_scripts]
+        hello = hello_world.cli:main
+
+        [gui_scripts]
+        hello-gui = hello_world.gui:main
+
+        [pytest11]
+        hello = hello_world.plugin
+        "###);
+
+        // The scripts must be declared in their own tables.
+        let contents = contents.replace(
+            "[project.entry-points.pytest11]",
+            "[project.entry-points.gui_scripts]",
+        );
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let err = pyproject_toml
+            .to_entry_points(temp_dir.path(), &EntryPoints::new())
+            .unwrap_err();
+        assert_snapshot!(err, @"The entry point group `gui_scripts` is reserved, use `project.scripts` or `project.gui-scripts` instead");
+    }
+
+    #[test]
+    fn summary_from_readme() {
+        let contents = indoc! {r###"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            readme = { text = "\n# Hello World\n\nA greeting.\n", content-type = "text/markdown" }
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            summary-from-readme = true
+        "###};
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_eq!(metadata.summary.as_deref(), Some("Hello World"));
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("\n# Hello World\n\nA greeting.\n")
+        );
+
+        // An explicit description takes precedence.
+        let contents = contents.replace(
+            "version = \"0.1.0\"",
+            "version = \"0.1.0\"\ndescription = \"A greeting\"",
+        );
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert_eq!(metadata.summary.as_deref(), Some("A greeting"));
+
+        assert_eq!(
+            readme_summary("=====\nHello\n=====\n\nWorld\n").as_deref(),
+            Some("Hello")
+        );
+        assert_eq!(readme_summary("\n  \n"), None);
+    }
+
+    #[test]
+    fn group_extras() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            dependencies = ["anyio", "idna"]
+
+            [project.optional-dependencies]
+            test = ["pytest>=8", "coverage"]
+            cli = ["click"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            group-extras = true
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        let grouped = pyproject_toml.format_metadata(&metadata);
+        let dependencies: Vec<&str> = grouped
+            .lines()
+            .filter(|line| {
+                line.starts_with("Requires-Dist: ") || line.starts_with("Provides-Extra: ")
+            })
+            .collect();
+        assert_eq!(
+            dependencies,
+            [
+                "Requires-Dist: anyio",
+                "Requires-Dist: idna",
+                "Provides-Extra: cli",
+                "Requires-Dist: click ; extra == 'cli'",
+                "Provides-Extra: test",
+                "Requires-Dist: pytest>=8 ; extra == 'test'",
+                "Requires-Dist: coverage ; extra == 'test'",
+            ]
+        );
+
+        // Grouping only reorders the headers.
+        assert_eq!(
+            Metadata23::parse(grouped.as_bytes()).unwrap(),
+            Metadata23::parse(metadata.core_metadata_format().as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn sort_extras() {
+        let contents = indoc! {r#"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+
+            [project.optional-dependencies]
+            test = ["pytest>=8"]
+            cli = ["click"]
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            group-extras = true
+            sort-extras = true
+        "#};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let mut metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        // Extras from the overlay are appended in the given order.
+        apply_overlay(
+            &mut metadata,
+            &[
+                ("Provides-Extra".to_string(), "Zeta".to_string()),
+                ("Provides-Extra".to_string(), "alpha".to_string()),
+                (
+                    "Requires-Dist".to_string(),
+                    "zstandard ; extra == 'zeta'".to_string(),
+                ),
+            ],
+        )
+        .unwrap();
+        assert_eq!(metadata.provides_extras, ["cli", "test", "Zeta", "alpha"]);
+
+        let dependencies: Vec<String> = pyproject_toml
+            .format_metadata(&metadata)
+            .lines()
+            .filter(|line| {
+                line.starts_with("Requires-Dist: ") || line.starts_with("Provides-Extra: ")
+            })
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            dependencies,
+            [
+                "Provides-Extra: alpha",
+                "Provides-Extra: cli",
+                "Requires-Dist: click ; extra == 'cli'",
+                "Provides-Extra: test",
+                "Requires-Dist: pytest>=8 ; extra == 'test'",
+                "Provides-Extra: Zeta",
+                "Requires-Dist: zstandard ; extra == 'zeta'",
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_variant() {
+        let contents = indoc! {r###"
+            [project]
+            name = "hello-world"
+            version = "0.1.0"
+            readme = { text = "# Hello", content-type = "text/markdown" }
+
+            [build-system]
+            requires = ["uv>=0.4.15,<5"]
+            build-backend = "uv"
+
+            [tool.uv.build-backend]
+            markdown-variant = "GFM"
+        "###};
+
+        let pyproject_toml = PyProjectToml::parse(contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert!(metadata
+            .core_metadata_format()
+            .contains("\nDescription-Content-Type: text/markdown; charset=UTF-8; variant=GFM\n"));
+
+        // The parameters in the content type take precedence over the setting.
+        let contents = contents.replace(
+            r#"content-type = "text/markdown""#,
+            r#"content-type = "text/markdown; charset=utf-8; variant=CommonMark""#,
+        );
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let metadata = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap();
+        assert!(metadata.core_metadata_format().contains(
+            "\nDescription-Content-Type: text/markdown; charset=UTF-8; variant=CommonMark\n"
+        ));
+
+        let contents = contents.replace("variant=CommonMark", "variant");
+        let pyproject_toml = PyProjectToml::parse(&contents).unwrap();
+        let err = pyproject_toml
+            .to_metadata(Path::new("/do/not/read"))
+            .unwrap_err();
+        assert_snapshot!(format_err(err), @r###"
+        Invalid pyproject.toml
+          Caused by: Invalid content type parameters, expected `; <key>=<value>` pairs: `text/markdown; charset=utf-8; variant`
+        "###);
+    }
+
+    #[test]
+    fn author_fields() {
+        let metadata = |authors: &str| {
+            let contents = formatdoc! {r#"
+                [project]
+                name = "hello-world"
+                version = "0.1.0"
+                authors = {authors}
+                maintainers = {authors}
+
+                [build-system]
+                requires = ["uv>=0.4.15,<5"]
+                build-backend = "uv"
+            "#};
+            PyProjectToml::parse(&contents)
+                .unwrap()
+                .to_metadata(Path::new("/do/not/read"))
+                .unwrap()
+                .core_metadata_format()
+        };
+
+        assert_snapshot!(metadata(r#"[{ name = "Ferris the crab" }]"#), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Author: Ferris the crab
+        Maintainer: Ferris the crab
+        "###);
+        assert_snapshot!(metadata(r#"[{ email = "ferris@example.net" }]"#), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Author-email: ferris@example.net
+        Maintainer-email: ferris@example.net
+        "###);
+        assert_snapshot!(metadata(r#"[{ name = "Ferris the crab", email = "ferris@example.net" }]"#), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Author-email: Ferris the crab <ferris@example.net>
+        Maintainer-email: Ferris the crab <ferris@example.net>
+        "###);
+        // An organization and a person.
+        assert_snapshot!(metadata(r#"[{ name = "Rust Foundation" }, { email = "ferris@example.net" }]"#), @r###"
+        Metadata-Version: 2.3
+        Name: hello-world
+        Version: 0.1.0
+        Author: Rust Foundation
+        Author-email: ferris@example.net
+        Maintainer: Rust Foundation
+        Maintainer-email: ferris@example.net
+        "###);
+    }
+}
@@ -0,0 +1,454 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Settings for the uv build backend.
+///
+/// The settings are read from the `[tool.uv.build-backend]` table in `pyproject.toml`.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct BuildBackendSettings {
+    /// The `Platform` entries of the core metadata, free-form names of the operating systems
+    /// supported by the distribution, e.g., `linux` or `ObscureUnix`.
+    ///
+    /// See <https://packaging.python.org/en/latest/specifications/core-metadata/#platform-multiple-use>.
+    pub(crate) platforms: Vec<String>,
+    /// The `Supported-Platform` entries of the core metadata, one per target the binary
+    /// distribution was compiled for, e.g., `RedHat 7.2` or `i386-win32-2791`.
+    ///
+    /// See <https://packaging.python.org/en/latest/specifications/core-metadata/#supported-platform-multiple-use>.
+    pub(crate) supported_platforms: Vec<String>,
+    /// The `Requires-External` entries of the core metadata, describing dependencies on the
+    /// system that are not Python packages, e.g., `C` or `libpng (>=1.5)`.
+    ///
+    /// See <https://packaging.python.org/en/latest/specifications/core-metadata/#requires-external-multiple-use>.
+    pub(crate) requires_external: Vec<String>,
+    /// The `Provides-Dist` entries of the core metadata, naming distributions contained in this
+    /// one, optionally with a version, e.g., `OtherProject` or `AnotherProject (3.4)`.
+    ///
+    /// See <https://packaging.python.org/en/latest/specifications/core-metadata/#provides-dist-multiple-use>.
+    pub(crate) provides_dist: Vec<String>,
+    /// The `Obsoletes-Dist` entries of the core metadata, naming distributions this one renders
+    /// obsolete, optionally with a version specifier, e.g., `Gorgon (<1.0)`.
+    ///
+    /// See <https://packaging.python.org/en/latest/specifications/core-metadata/#obsoletes-dist-multiple-use>.
+    pub(crate) obsoletes_dist: Vec<String>,
+    /// Whether to package test files.
+    ///
+    /// By default, files following the common test conventions are excluded from the wheel:
+    /// `test_*.py`, `*_test.py`, `conftest.py` and anything in a `tests` directory.
+    pub(crate) include_tests: bool,
+    /// The `[project]` fields to inherit from the workspace root, e.g., `["version", "authors"]`.
+    ///
+    /// The workspace root is the closest parent directory with a `pyproject.toml` that contains a
+    /// `[tool.uv.workspace]` table. Fields set in the project itself take precedence over the
+    /// inherited values. Paths in inherited fields stay relative to the project, not to the
+    /// workspace root.
+    pub(crate) inherit: Vec<String>,
+    /// Read the version from a module attribute or a file instead of `project.version`, e.g.,
+    /// `attr:foo.__version__` or `file:VERSION`.
+    ///
+    /// The attribute must be assigned a plain string literal, e.g., `__version__ = "1.2.3"`. The
+    /// module is read, not executed, so computed versions are rejected. The file, relative to the
+    /// project root, contains only the version.
+    pub(crate) version_source: Option<String>,
+    /// Whether to byte-compile each packaged Python file and fail the build on syntax errors.
+    ///
+    /// This requires a Python interpreter, which is looked up as `python3` (`python` on Windows)
+    /// on the `PATH`.
+    pub(crate) check_syntax: bool,
+    /// Whether to fail the build on warnings, e.g., about the `[build-system]` table.
+    pub(crate) strict: bool,
+    /// The encoding of the hashes in `RECORD`.
+    ///
+    /// The wheel specification requires urlsafe base64 without padding, hex is only for legacy
+    /// tools that can't read anything else.
+    pub(crate) record_hash_encoding: RecordHashEncoding,
+    /// The directories to add to `sys.path` for editable installs, relative to the project root,
+    /// e.g., `["src", "generated"]`.
+    ///
+    /// Defaults to `src`.
+    pub(crate) source_roots: Vec<String>,
+    /// Whether to derive `Requires-Python` from the `Programming Language :: Python :: 3.x`
+    /// classifiers if `project.requires-python` is missing.
+    ///
+    /// The lowest listed version becomes the lower bound, e.g., `>=3.8`. An explicit
+    /// `project.requires-python` always takes precedence.
+    pub(crate) infer_requires_python: bool,
+    /// What to do with files whose names are not valid UTF-8, which can't be stored in a wheel.
+    pub(crate) non_utf8_filenames: NonUtf8Filenames,
+    /// Whether to also write the deprecated `Home-page` and `Download-URL` core metadata fields,
+    /// from the `Homepage` and `Download` entries of `project.urls`, for old indexes.
+    ///
+    /// The URLs are always written as `Project-URL`.
+    pub(crate) legacy_urls: bool,
+    /// A constraints file, relative to the project root, that narrows the version specifiers of
+    /// the emitted `Requires-Dist` entries, e.g., `constraints.txt` containing `anyio<2.0`.
+    ///
+    /// A dependency on `anyio>=1.0` is then written as `anyio>=1.0, <2.0`. The constraints don't
+    /// add dependencies, packages that the project doesn't depend on are ignored.
+    pub(crate) constraints: Option<PathBuf>,
+    /// Whether to write a JSON lines log of the build steps, the packaged files and the warnings
+    /// next to the wheel, as `<wheel name without extension>.build-log.jsonl`.
+    ///
+    /// The log is written for failed builds, too, but it is never included in the wheel.
+    pub(crate) build_log: bool,
+    /// How to handle symlinks to files and directories in the module.
+    pub(crate) symlinks: Symlinks,
+    /// Force the `Root-Is-Purelib` value in the `WHEEL` file.
+    ///
+    /// By default, it is `true` for wheels without platform tags and `false` otherwise.
+    pub(crate) root_is_purelib: Option<bool>,
+    /// How glob patterns, such as `project.license-files`, match files.
+    pub(crate) glob_options: GlobOptions,
+    /// Build a PEP 561 stub-only distribution, which contains only `.pyi` files and `py.typed`.
+    ///
+    /// The distribution should be named `<module>-stubs`, the stubs are read from
+    /// `src/<module>-stubs`.
+    pub(crate) stubs: bool,
+    /// Write this `Metadata-Version` instead of the default, e.g., `2.1`, to test how installers
+    /// handle older metadata.
+    ///
+    /// By default, `2.3` is used, or `2.4` if the project uses the PEP 639 fields
+    /// `License-Expression` or `License-File`.
+    ///
+    /// The build fails if the project uses a field that the version doesn't support yet, e.g.,
+    /// `License-Expression` before 2.4.
+    pub(crate) metadata_version: Option<String>,
+    /// Files to install outside the package, mapping the path relative to the project root to
+    /// its destination, e.g., `"share/foo.conf" = "{data}/etc/foo.conf"`.
+    ///
+    /// The destination starts with the install location as template variable: `{purelib}`,
+    /// `{platlib}`, `{headers}`, `{scripts}` or `{data}`. The files are added to the matching
+    /// category of the `.data` directory of the wheel.
+    pub(crate) data_files: BTreeMap<String, String>,
+    /// Entry point groups whose object references are not validated, e.g.,
+    /// `["myframework.plugins"]`, for plugin frameworks that generate the target modules when
+    /// the package is installed or run.
+    pub(crate) dynamic_entry_point_groups: Vec<String>,
+    /// The maximum depth of directories below the module root, counting directories reached
+    /// through symlinks.
+    ///
+    /// The build fails for deeper files. Defaults to 64.
+    pub(crate) max_walk_depth: Option<usize>,
+    /// The markdown variant of a markdown readme, added to the `Description-Content-Type`,
+    /// e.g., `text/markdown; charset=UTF-8; variant=GFM`.
+    pub(crate) markdown_variant: Option<MarkdownVariant>,
+    /// How strongly to compress the files in the wheel.
+    ///
+    /// Better compression produces smaller wheels, e.g., for large packages with data files, at
+    /// the cost of build time. Editable wheels are always stored without compression.
+    pub(crate) compression_level: CompressionLevel,
+    /// A file with additional entry points in the ini-like `entry_points.txt` format, relative to
+    /// the project root, e.g., `entry_points.ini` when migrating from setuptools.
+    ///
+    /// The entry points are merged with `project.scripts` and `project.entry-points`.
+    pub(crate) entry_points_file: Option<PathBuf>,
+    /// Where the module is located, `src/<module>` or `<module>` in the project root.
+    ///
+    /// By default, the src layout is used if the project has a `src` directory, and the flat
+    /// layout otherwise.
+    pub(crate) layout: Option<Layout>,
+    /// Glob patterns of the module files to package, relative to the source root, e.g.,
+    /// `["foo/**/*.py", "foo/data/*.json"]`.
+    ///
+    /// By default, all files in the module are packaged. A glob that matches no files is reported
+    /// as warning.
+    pub(crate) include: Vec<String>,
+    /// Glob patterns of module files to leave out of the wheel, relative to the source root,
+    /// e.g., `["foo/**/*.md"]`.
+    ///
+    /// VCS directories and bytecode are always excluded.
+    pub(crate) exclude: Vec<String>,
+    /// A `MANIFEST.in`-style file, relative to the project root, to ease the migration from
+    /// setuptools, e.g., `MANIFEST.in` containing `recursive-include src/foo/data *.json`.
+    ///
+    /// The `include`, `exclude`, `recursive-include`, `recursive-exclude`, `global-include`,
+    /// `global-exclude`, `graft` and `prune` directives are applied in order after `include` and
+    /// `exclude`, with patterns relative to the project root. Only files in the module are
+    /// packaged.
+    pub(crate) manifest: Option<PathBuf>,
+    /// Whether to write each `Provides-Extra` directly before the `Requires-Dist` entries of
+    /// that extra, after the base dependencies, to make the metadata easier to read.
+    ///
+    /// By default, all `Requires-Dist` entries are written in order, followed by the
+    /// `Provides-Extra` entries.
+    pub(crate) group_extras: bool,
+    /// Whether to sort the `Provides-Extra` entries by their normalized name, for stable metadata
+    /// when extras are added in differing order, e.g., through a metadata overlay.
+    ///
+    /// The order of the `Requires-Dist` entries of each extra is kept.
+    pub(crate) sort_extras: bool,
+    /// Whether to use the first line of the readme as `Summary` if `project.description` is
+    /// missing, e.g., `Hello World` for a markdown readme starting with `# Hello World`.
+    ///
+    /// The full readme is still the `Description`.
+    pub(crate) summary_from_readme: bool,
+    /// Where to put the `project.license-files` in the `.dist-info` directory of the wheel.
+    ///
+    /// PEP 639 places them in `licenses`, the dist-info root is only for older tools that expect
+    /// them there.
+    pub(crate) license_dir: LicenseDir,
+    /// The dotted import name of a module in a PEP 420 namespace package, e.g., `acme.plugin`
+    /// for the `acme` namespace that is shared with other distributions.
+    ///
+    /// The module is read from `src/acme/plugin`. The namespace directories, `src/acme`, have no
+    /// `__init__.py` and only the module itself is packaged.
+    pub(crate) namespace: Option<String>,
+    /// The Unix permissions of the regular files in the wheel and the source distribution, as
+    /// octal number, e.g., `0o600` for files only the owner can read.
+    ///
+    /// Defaults to `0o644`.
+    pub(crate) default_file_mode: Option<u16>,
+    /// The Unix permissions of executable files, such as the scripts in `.data/scripts`, as octal
+    /// number, e.g., `0o700`.
+    ///
+    /// Defaults to `0o755`.
+    pub(crate) default_executable_mode: Option<u16>,
+    /// Record a hash over the paths and contents of the packaged files in
+    /// `.dist-info/uv_build_metadata.json`, e.g., for provenance.
+    ///
+    /// The fingerprint only depends on the files, not on their order or compression in the
+    /// archive, so identical sources always have the same fingerprint.
+    pub(crate) source_fingerprint: bool,
+    /// Skip module files larger than this many bytes with a warning, e.g., to catch datasets
+    /// that were accidentally placed in the module.
+    ///
+    /// By default, files of any size are packaged.
+    pub(crate) max_file_size: Option<u64>,
+    /// Files to add to a category of the `.data` directory of the wheel, mapping `purelib`,
+    /// `platlib`, `headers`, `scripts` or `data` to globs relative to the project root, e.g.,
+    /// `headers = ["include/*.h"]`.
+    ///
+    /// The files are placed relative to the directory before the first wildcard of the glob, so
+    /// `include/foo/*.h` adds `include/foo/bar.h` as `<name>-<version>.data/headers/bar.h`. A
+    /// glob that matches no files is reported as warning.
+    pub(crate) data: BTreeMap<String, Vec<String>>,
+    /// The order of the fields in `METADATA` and `PKG-INFO`.
+    pub(crate) metadata_field_order: MetadataFieldOrder,
+}
+
+impl BuildBackendSettings {
+    /// The directory that contains the module: `src` in the src layout, the project root in the
+    /// flat layout.
+    pub(crate) fn source_root(&self, root: &Path) -> PathBuf {
+        let layout = self.layout.unwrap_or_else(|| {
+            if root.join("src").is_dir() {
+                Layout::Src
+            } else {
+                Layout::Flat
+            }
+        });
+        match layout {
+            Layout::Src => root.join("src"),
+            Layout::Flat => root.to_path_buf(),
+        }
+    }
+}
+
+/// The keys of a settings table, e.g., `strict` for [`BuildBackendSettings`].
+///
+/// The names are read from the struct's `Deserialize` implementation, so they can't go stale.
+pub(crate) fn setting_names<T: for<'de> Deserialize<'de>>() -> &'static [&'static str] {
+    /// A deserializer that records the field names it's asked for and fails.
+    struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> serde::Deserializer<'de> for FieldNames<'_> {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: serde::de::Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("expected a struct"))
+        }
+
+        fn deserialize_struct<V: serde::de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(serde::de::Error::custom("only reading the field names"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+            option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+            ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// How glob patterns match files, mirroring [`glob::MatchOptions`].
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct GlobOptions {
+    /// Whether `LICENSE` and `license` are different, independent of whether the file system is
+    /// case-sensitive.
+    ///
+    /// Defaults to `true`, so that the same files are matched on all platforms. Path components
+    /// without wildcards are looked up directly, so they still follow the file system.
+    pub(crate) case_sensitive: bool,
+}
+
+impl Default for GlobOptions {
+    fn default() -> Self {
+        Self {
+            case_sensitive: true,
+        }
+    }
+}
+
+impl GlobOptions {
+    pub(crate) fn match_options(self) -> glob::MatchOptions {
+        glob::MatchOptions {
+            case_sensitive: self.case_sensitive,
+            ..glob::MatchOptions::new()
+        }
+    }
+}
+
+/// Where the module is located in the project.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Layout {
+    /// The module is at `src/<module>`.
+    Src,
+    /// The module is at `<module>`, next to `pyproject.toml`.
+    Flat,
+}
+
+/// How to handle symlinks in the module.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Symlinks {
+    /// Package the target of the symlink under the path of the symlink.
+    ///
+    /// For a symlinked directory, its contents are packaged below the symlink's import path.
+    #[default]
+    Follow,
+    /// Leave symlinks out of the wheel.
+    Skip,
+    /// Fail the build.
+    Error,
+}
+
+/// The markdown variants registered for `text/markdown`, see RFC 7763.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MarkdownVariant {
+    /// GitHub-flavored markdown.
+    #[serde(rename = "GFM")]
+    Gfm,
+    /// <https://commonmark.org/>
+    CommonMark,
+}
+
+impl MarkdownVariant {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Gfm => "GFM",
+            Self::CommonMark => "CommonMark",
+        }
+    }
+}
+
+/// How to handle files whose names are not valid UTF-8.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum NonUtf8Filenames {
+    /// Fail the build.
+    #[default]
+    Error,
+    /// Leave the file out of the wheel and show a warning.
+    Skip,
+}
+
+/// The compression of the files in the wheel.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum CompressionLevel {
+    /// No compression.
+    Stored,
+    /// Deflate with a low level, favoring speed over size.
+    ///
+    /// Builds spend less CPU time compressing, e.g., for CPU-bound CI, at the cost of larger
+    /// wheels than with `default`.
+    Fast,
+    /// Deflate with the default level.
+    #[default]
+    Default,
+    /// Deflate, favoring size over speed.
+    Best,
+}
+
+/// The order of the core metadata fields.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum MetadataFieldOrder {
+    /// The order of the core metadata specification, as documented by PyPI: `Metadata-Version`,
+    /// `Name`, `Version`, then the other fields.
+    #[default]
+    Specification,
+    /// The order of older uv versions, with e.g. `Requires-Python` after the maintainers.
+    Legacy,
+}
+
+/// The location of the license files in the `.dist-info` directory.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum LicenseDir {
+    /// `.dist-info/licenses/`, as specified by PEP 639.
+    #[default]
+    Licenses,
+    /// The `.dist-info` root, which is not standard compliant.
+    Root,
+    /// Both `.dist-info/licenses/` and the `.dist-info` root.
+    Both,
+}
+
+/// The Unix permissions of the files in built archives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileModes {
+    /// The mode of regular files.
+    pub(crate) file: u16,
+    /// The mode of executable files.
+    pub(crate) executable: u16,
+}
+
+impl Default for FileModes {
+    fn default() -> Self {
+        Self {
+            file: 0o644,
+            executable: 0o755,
+        }
+    }
+}
+
+/// The encoding of the hashes in `RECORD`.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum RecordHashEncoding {
+    /// Urlsafe base64 without padding, as required by the wheel specification.
+    #[default]
+    Base64,
+    /// Lowercase hex, which is not standard compliant.
+    Hex,
+}
+
+impl RecordHashEncoding {
+    /// Format a SHA-256 digest as `RECORD` hash, e.g., `sha256=<encoded digest>`.
+    pub(crate) fn format(self, digest: &[u8]) -> String {
+        match self {
+            Self::Base64 => format!("sha256={}", URL_SAFE_NO_PAD.encode(digest)),
+            Self::Hex => format!("sha256={}", hex::encode(digest)),
+        }
+    }
+}
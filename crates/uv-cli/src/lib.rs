@@ -1351,6 +1351,16 @@ pub struct PipSyncArgs {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Write the `file://` URLs of local directories and archives in `direct_url.json` relative
+    /// to the given directory, e.g., for development containers that mount the project at
+    /// different locations.
+    ///
+    /// The directory is recorded as `base_url` next to the relative URL, so uv still recognizes
+    /// the installed packages. By default, the URLs are absolute, as required by the direct URL
+    /// data structure; other tools may not understand relative URLs.
+    #[arg(long, env = "UV_DIRECT_URL_BASE")]
+    pub direct_url_base: Option<PathBuf>,
+
     #[command(flatten)]
     pub compat_args: compat::PipSyncCompatArgs,
 }
@@ -1626,6 +1636,16 @@ pub struct PipInstallArgs {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Write the `file://` URLs of local directories and archives in `direct_url.json` relative
+    /// to the given directory, e.g., for development containers that mount the project at
+    /// different locations.
+    ///
+    /// The directory is recorded as `base_url` next to the relative URL, so uv still recognizes
+    /// the installed packages. By default, the URLs are absolute, as required by the direct URL
+    /// data structure; other tools may not understand relative URLs.
+    #[arg(long, env = "UV_DIRECT_URL_BASE")]
+    pub direct_url_base: Option<PathBuf>,
+
     #[command(flatten)]
     pub compat_args: compat::PipInstallCompatArgs,
 }
@@ -9,12 +9,29 @@ pub struct Archive {
     pub id: ArchiveId,
     /// The computed hashes of the archive.
     pub hashes: Vec<HashDigest>,
+    /// Whether the wheel was yanked from the registry when it was downloaded.
+    ///
+    /// The yank status is fixed at download time: a wheel that is yanked after it was cached
+    /// isn't marked until the cache entry is refreshed.
+    #[serde(default)]
+    pub yanked: bool,
 }
 
 impl Archive {
     /// Create a new [`Archive`] with the given ID and hashes.
     pub(crate) fn new(id: ArchiveId, hashes: Vec<HashDigest>) -> Self {
-        Self { id, hashes }
+        Self {
+            id,
+            hashes,
+            yanked: false,
+        }
+    }
+
+    /// Set whether the wheel was yanked from the registry.
+    #[must_use]
+    pub(crate) fn with_yanked(mut self, yanked: bool) -> Self {
+        self.yanked = yanked;
+        self
     }
 
     /// Returns `true` if the archive exists in the cache.
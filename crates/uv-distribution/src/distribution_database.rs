@@ -20,7 +20,7 @@ use distribution_types::{
     SourceDist,
 };
 use platform_tags::Tags;
-use pypi_types::HashDigest;
+use pypi_types::{HashDigest, Yanked};
 use uv_cache::{ArchiveId, CacheBucket, CacheEntry, WheelCache};
 use uv_cache_info::{CacheInfo, Timestamp};
 use uv_client::{
@@ -480,6 +480,12 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         // Create an entry for the HTTP cache.
         let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
 
+        // Record the yank status, to skip yanked wheels when reusing the cache.
+        let yanked = dist
+            .file()
+            .and_then(|file| file.yanked.as_ref())
+            .is_some_and(Yanked::is_yanked);
+
         let download = |response: reqwest::Response| {
             async {
                 let size = size.or_else(|| content_length(&response));
@@ -530,10 +536,10 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                     reporter.on_download_complete(dist.name(), progress);
                 }
 
-                Ok(Archive::new(
-                    id,
-                    hashers.into_iter().map(HashDigest::from).collect(),
-                ))
+                Ok(
+                    Archive::new(id, hashers.into_iter().map(HashDigest::from).collect())
+                        .with_yanked(yanked),
+                )
             }
             .instrument(info_span!("wheel", wheel = %dist))
         };
@@ -602,6 +608,12 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         // Create an entry for the HTTP cache.
         let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
 
+        // Record the yank status, to skip yanked wheels when reusing the cache.
+        let yanked = dist
+            .file()
+            .and_then(|file| file.yanked.as_ref())
+            .is_some_and(Yanked::is_yanked);
+
         let download = |response: reqwest::Response| {
             async {
                 let size = size.or_else(|| content_length(&response));
@@ -687,7 +699,7 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                     reporter.on_download_complete(dist.name(), progress);
                 }
 
-                Ok(Archive::new(id, hashes))
+                Ok(Archive::new(id, hashes).with_yanked(yanked))
             }
             .instrument(info_span!("wheel", wheel = %dist))
         };
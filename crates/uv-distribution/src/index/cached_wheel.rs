@@ -19,6 +19,8 @@ pub struct CachedWheel {
     pub hashes: Vec<HashDigest>,
     /// The [`CacheInfo`] for the wheel.
     pub cache_info: CacheInfo,
+    /// Whether the wheel was yanked from the registry when it was downloaded.
+    pub yanked: bool,
 }
 
 impl CachedWheel {
@@ -40,6 +42,7 @@ impl CachedWheel {
             entry,
             hashes,
             cache_info,
+            yanked: false,
         })
     }
 
@@ -103,7 +106,7 @@ impl CachedWheel {
         // Read the pointer.
         let pointer = HttpArchivePointer::read_from(path).ok()??;
         let cache_info = pointer.to_cache_info();
-        let Archive { id, hashes } = pointer.into_archive();
+        let Archive { id, hashes, yanked } = pointer.into_archive();
 
         let entry = cache.entry(CacheBucket::Archive, "", id);
 
@@ -113,6 +116,7 @@ impl CachedWheel {
             entry,
             hashes,
             cache_info,
+            yanked,
         })
     }
 
@@ -127,7 +131,7 @@ impl CachedWheel {
         // Read the pointer.
         let pointer = LocalArchivePointer::read_from(path).ok()??;
         let cache_info = pointer.to_cache_info();
-        let Archive { id, hashes } = pointer.into_archive();
+        let Archive { id, hashes, yanked } = pointer.into_archive();
 
         // Convert to a cached wheel.
         let entry = cache.entry(CacheBucket::Archive, "", id);
@@ -136,6 +140,7 @@ impl CachedWheel {
             entry,
             hashes,
             cache_info,
+            yanked,
         })
     }
 
@@ -1,5 +1,5 @@
 pub use built_wheel_index::BuiltWheelIndex;
-pub use registry_wheel_index::RegistryWheelIndex;
+pub use registry_wheel_index::{RegistryWheelIndex, SharedRegistryWheelIndex};
 
 mod built_wheel_index;
 mod cached_wheel;
@@ -1,8 +1,14 @@
 use std::collections::hash_map::Entry;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
-use rustc_hash::FxHashMap;
+use dashmap::DashMap;
+use rustc_hash::{FxBuildHasher, FxHashMap};
+use tracing::trace;
 
 use distribution_types::{CachedRegistryDist, Hashed, IndexLocations, IndexUrl};
+use pep440_rs::Version;
 use platform_tags::Tags;
 use uv_cache::{Cache, CacheBucket, WheelCache};
 use uv_fs::{directories, files, symlinks};
@@ -10,7 +16,9 @@ use uv_normalize::PackageName;
 use uv_types::HashStrategy;
 
 use crate::index::cached_wheel::CachedWheel;
-use crate::source::{HttpRevisionPointer, LocalRevisionPointer, HTTP_REVISION, LOCAL_REVISION};
+use crate::source::{
+    HttpRevisionPointer, LocalRevisionPointer, Revision, HTTP_REVISION, LOCAL_REVISION,
+};
 
 /// An entry in the [`RegistryWheelIndex`].
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -19,6 +27,12 @@ pub struct IndexEntry {
     pub dist: CachedRegistryDist,
     /// Whether the wheel was built from source (true), or downloaded from the registry directly (false).
     pub built: bool,
+    /// Whether the wheel, or the source distribution it was built from, was yanked from the
+    /// registry.
+    ///
+    /// The yank status is recorded when the file is downloaded, so a file that was yanked after it
+    /// was cached is not marked as yanked.
+    pub yanked: bool,
 }
 
 /// A local index of distributions that originate from a registry, like `PyPI`.
@@ -28,23 +42,49 @@ pub struct RegistryWheelIndex<'a> {
     tags: &'a Tags,
     index_locations: &'a IndexLocations,
     hasher: &'a HashStrategy,
+    /// Skip cache pointers that were written longer ago than this.
+    max_age: Option<Duration>,
+    /// Keep yanked wheels, marked with [`IndexEntry::yanked`].
+    include_yanked: bool,
     index: FxHashMap<&'a PackageName, Vec<IndexEntry>>,
+    /// Packages indexed from a single index they are pinned to.
+    pinned: FxHashMap<(&'a PackageName, &'a IndexUrl), Vec<IndexEntry>>,
 }
 
 impl<'a> RegistryWheelIndex<'a> {
     /// Initialize an index of registry distributions.
+    ///
+    /// Yanked wheels are skipped, unless they are included with
+    /// [`RegistryWheelIndex::with_yanked`].
+    ///
+    /// With a `max_age`, wheels whose cache pointer is older are skipped, so long-lived caches
+    /// expire without being cleared manually.
     pub fn new(
         cache: &'a Cache,
         tags: &'a Tags,
         index_locations: &'a IndexLocations,
         hasher: &'a HashStrategy,
+        max_age: Option<Duration>,
     ) -> Self {
         Self {
             cache,
             tags,
             index_locations,
             hasher,
+            max_age,
+            include_yanked: false,
             index: FxHashMap::default(),
+            pinned: FxHashMap::default(),
+        }
+    }
+
+    /// Include yanked wheels, marked with [`IndexEntry::yanked`], so the caller can still use
+    /// them for a version the user pinned explicitly.
+    #[must_use]
+    pub fn with_yanked(self, include_yanked: bool) -> Self {
+        Self {
+            include_yanked,
+            ..self
         }
     }
 
@@ -55,6 +95,49 @@ impl<'a> RegistryWheelIndex<'a> {
         self.get_impl(name).iter().rev()
     }
 
+    /// Return the best available wheel for a given package version, e.g., to check a pin from a
+    /// lockfile.
+    ///
+    /// If the package is not yet indexed, this will index the package by reading from the cache.
+    /// The entries are sorted by version, so the version is found with a binary search.
+    pub fn get_version(
+        &mut self,
+        name: &'a PackageName,
+        version: &Version,
+    ) -> Option<&CachedRegistryDist> {
+        let entries = self.get_impl(name);
+        // The best wheel of a version is the last entry of the version.
+        let end = entries.partition_point(|entry| entry.dist.filename.version <= *version);
+        let entry = entries[..end].last()?;
+        (entry.dist.filename.version == *version).then_some(&entry.dist)
+    }
+
+    /// Return an iterator over available wheels for a given package, reading only the cache of
+    /// the index the package is pinned to.
+    ///
+    /// If the package is not yet indexed for this index, this will index the package by reading
+    /// from the cache.
+    pub fn get_from(
+        &mut self,
+        name: &'a PackageName,
+        index_url: &'a IndexUrl,
+    ) -> impl Iterator<Item = &IndexEntry> {
+        let versions = match self.pinned.entry((name, index_url)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Self::index(
+                name,
+                self.cache,
+                self.tags,
+                self.index_locations,
+                Some(index_url),
+                self.hasher,
+                self.max_age,
+                self.include_yanked,
+            )),
+        };
+        versions.iter().rev()
+    }
+
     /// Get an entry in the index.
     fn get_impl(&mut self, name: &'a PackageName) -> &[IndexEntry] {
         let versions = match self.index.entry(name) {
@@ -64,19 +147,27 @@ impl<'a> RegistryWheelIndex<'a> {
                 self.cache,
                 self.tags,
                 self.index_locations,
+                None,
                 self.hasher,
+                self.max_age,
+                self.include_yanked,
             )),
         };
         versions
     }
 
     /// Add a package to the index by reading from the cache.
+    ///
+    /// If the package is pinned to an index, only the cache of that index is read.
     fn index(
         package: &PackageName,
         cache: &Cache,
         tags: &Tags,
         index_locations: &IndexLocations,
+        pinned: Option<&IndexUrl>,
         hasher: &HashStrategy,
+        max_age: Option<Duration>,
+        include_yanked: bool,
     ) -> Vec<IndexEntry> {
         let mut entries = vec![];
 
@@ -86,12 +177,22 @@ impl<'a> RegistryWheelIndex<'a> {
             .map(|flat_index| IndexUrl::from(flat_index.clone()))
             .collect();
 
-        for index_url in index_locations.indexes().chain(flat_index_urls.iter()) {
+        let index_urls: Vec<&IndexUrl> = if let Some(index_url) = pinned {
+            vec![index_url]
+        } else {
+            index_locations
+                .indexes()
+                .chain(flat_index_urls.iter())
+                .collect()
+        };
+
+        for index_url in index_urls {
             // Index all the wheels that were downloaded directly from the registry.
             let wheel_dir = cache.shard(
                 CacheBucket::Wheels,
                 WheelCache::Index(index_url).wheel_dir(package.to_string()),
             );
+            trace!("Scanning cache directory: {}", wheel_dir.display());
 
             // For registry wheels, the cache structure is: `<index>/<package-name>/<wheel>.http`
             // or `<index>/<package-name>/<version>/<wheel>.rev`.
@@ -103,23 +204,23 @@ impl<'a> RegistryWheelIndex<'a> {
                             .extension()
                             .is_some_and(|ext| ext.eq_ignore_ascii_case("http"))
                         {
-                            if let Some(wheel) =
-                                CachedWheel::from_http_pointer(wheel_dir.join(file), cache)
-                            {
-                                if wheel.filename.compatibility(tags).is_compatible() {
-                                    // Enforce hash-checking based on the built distribution.
-                                    if wheel.satisfies(
-                                        hasher.get_package(
-                                            &wheel.filename.name,
-                                            &wheel.filename.version,
-                                        ),
-                                    ) {
-                                        entries.push(IndexEntry {
-                                            dist: wheel.into_registry_dist(),
-                                            built: false,
-                                        });
-                                    }
-                                }
+                            let path = wheel_dir.join(file);
+                            if is_expired(&path, max_age) {
+                                trace!("Skipping expired wheel pointer: {}", path.display());
+                                continue;
+                            }
+                            trace!("Reading wheel pointer: {}", path.display());
+                            if let Some(wheel) = CachedWheel::from_http_pointer(&path, cache) {
+                                Self::add_wheel(
+                                    wheel,
+                                    None,
+                                    tags,
+                                    hasher,
+                                    include_yanked,
+                                    &mut entries,
+                                );
+                            } else {
+                                trace!("Skipping unreadable wheel pointer: {}", path.display());
                             }
                         }
                     }
@@ -129,23 +230,23 @@ impl<'a> RegistryWheelIndex<'a> {
                             .extension()
                             .is_some_and(|ext| ext.eq_ignore_ascii_case("rev"))
                         {
-                            if let Some(wheel) =
-                                CachedWheel::from_local_pointer(wheel_dir.join(file), cache)
-                            {
-                                if wheel.filename.compatibility(tags).is_compatible() {
-                                    // Enforce hash-checking based on the built distribution.
-                                    if wheel.satisfies(
-                                        hasher.get_package(
-                                            &wheel.filename.name,
-                                            &wheel.filename.version,
-                                        ),
-                                    ) {
-                                        entries.push(IndexEntry {
-                                            dist: wheel.into_registry_dist(),
-                                            built: false,
-                                        });
-                                    }
-                                }
+                            let path = wheel_dir.join(file);
+                            if is_expired(&path, max_age) {
+                                trace!("Skipping expired wheel pointer: {}", path.display());
+                                continue;
+                            }
+                            trace!("Reading wheel pointer: {}", path.display());
+                            if let Some(wheel) = CachedWheel::from_local_pointer(&path, cache) {
+                                Self::add_wheel(
+                                    wheel,
+                                    None,
+                                    tags,
+                                    hasher,
+                                    include_yanked,
+                                    &mut entries,
+                                );
+                            } else {
+                                trace!("Skipping unreadable wheel pointer: {}", path.display());
                             }
                         }
                     }
@@ -158,6 +259,7 @@ impl<'a> RegistryWheelIndex<'a> {
                 CacheBucket::SourceDistributions,
                 WheelCache::Index(index_url).wheel_dir(package.to_string()),
             );
+            trace!("Scanning cache directory: {}", cache_shard.display());
 
             // For registry wheels, the cache structure is: `<index>/<package-name>/<version>/`.
             for shard in directories(&cache_shard) {
@@ -169,6 +271,17 @@ impl<'a> RegistryWheelIndex<'a> {
                     // Add files from remote registries.
                     IndexUrl::Pypi(_) | IndexUrl::Url(_) => {
                         let revision_entry = cache_shard.entry(HTTP_REVISION);
+                        if is_expired(revision_entry.path(), max_age) {
+                            trace!(
+                                "Skipping expired revision pointer: {}",
+                                revision_entry.path().display()
+                            );
+                            continue;
+                        }
+                        trace!(
+                            "Reading revision pointer: {}",
+                            revision_entry.path().display()
+                        );
                         if let Ok(Some(pointer)) = HttpRevisionPointer::read_from(revision_entry) {
                             Some(pointer.into_revision())
                         } else {
@@ -178,6 +291,17 @@ impl<'a> RegistryWheelIndex<'a> {
                     // Add files from local registries (e.g., `--find-links`).
                     IndexUrl::Path(_) => {
                         let revision_entry = cache_shard.entry(LOCAL_REVISION);
+                        if is_expired(revision_entry.path(), max_age) {
+                            trace!(
+                                "Skipping expired revision pointer: {}",
+                                revision_entry.path().display()
+                            );
+                            continue;
+                        }
+                        trace!(
+                            "Reading revision pointer: {}",
+                            revision_entry.path().display()
+                        );
                         if let Ok(Some(pointer)) = LocalRevisionPointer::read_from(revision_entry) {
                             Some(pointer.into_revision())
                         } else {
@@ -188,21 +312,24 @@ impl<'a> RegistryWheelIndex<'a> {
 
                 if let Some(revision) = revision {
                     for wheel_dir in symlinks(cache_shard.join(revision.id())) {
-                        if let Some(wheel) = CachedWheel::from_built_source(wheel_dir) {
-                            if wheel.filename.compatibility(tags).is_compatible() {
-                                // Enforce hash-checking based on the source distribution.
-                                if revision.satisfies(
-                                    hasher
-                                        .get_package(&wheel.filename.name, &wheel.filename.version),
-                                ) {
-                                    entries.push(IndexEntry {
-                                        dist: wheel.into_registry_dist(),
-                                        built: true,
-                                    });
-                                }
-                            }
+                        if let Some(wheel) = CachedWheel::from_built_source(&wheel_dir) {
+                            Self::add_wheel(
+                                wheel,
+                                Some(&revision),
+                                tags,
+                                hasher,
+                                include_yanked,
+                                &mut entries,
+                            );
+                        } else {
+                            trace!("Skipping unreadable built wheel: {}", wheel_dir.display());
                         }
                     }
+                } else {
+                    trace!(
+                        "Skipping source distribution without a readable revision: {}",
+                        cache_shard.display()
+                    );
                 }
             }
         }
@@ -226,4 +353,601 @@ impl<'a> RegistryWheelIndex<'a> {
 
         entries
     }
+
+    /// Add the [`CachedWheel`] to the index, if it's compatible with the platform, satisfies
+    /// the hash policy, and is not yanked or yanked wheels are included.
+    ///
+    /// Wheels downloaded from the registry are checked against their own hashes and carry their
+    /// own yank status, while wheels built from a source distribution use those of the source
+    /// distribution revision.
+    fn add_wheel(
+        wheel: CachedWheel,
+        built_from: Option<&Revision>,
+        tags: &Tags,
+        hasher: &HashStrategy,
+        include_yanked: bool,
+        entries: &mut Vec<IndexEntry>,
+    ) {
+        if !wheel.filename.compatibility(tags).is_compatible() {
+            trace!("Rejecting wheel with incompatible tags: {}", wheel.filename);
+            return;
+        }
+
+        // Enforce hash-checking based on the built distribution or the source distribution.
+        let hash_policy = hasher.get_package(&wheel.filename.name, &wheel.filename.version);
+        let satisfies = match built_from {
+            Some(revision) => revision.satisfies(hash_policy),
+            None => wheel.satisfies(hash_policy),
+        };
+        if !satisfies {
+            trace!("Rejecting wheel with mismatched hashes: {}", wheel.filename);
+            return;
+        }
+
+        let yanked = built_from.map_or(wheel.yanked, Revision::yanked);
+        if yanked {
+            if !include_yanked {
+                trace!("Rejecting yanked wheel: {}", wheel.filename);
+                return;
+            }
+            trace!("Accepting yanked wheel: {}", wheel.filename);
+        } else {
+            trace!("Accepting wheel: {}", wheel.filename);
+        }
+        entries.push(IndexEntry {
+            dist: wheel.into_registry_dist(),
+            built: built_from.is_some(),
+            yanked,
+        });
+    }
+}
+
+/// Whether the cache pointer was written longer ago than `max_age`.
+fn is_expired(path: &Path, max_age: Option<Duration>) -> bool {
+    let Some(max_age) = max_age else {
+        return false;
+    };
+    let Ok(modified) = fs_err::metadata(path).and_then(|metadata| metadata.modified()) else {
+        return false;
+    };
+    modified.elapsed().is_ok_and(|age| age > max_age)
+}
+
+/// A [`RegistryWheelIndex`] that can be shared across threads, e.g., for concurrent install
+/// planning.
+///
+/// Packages are indexed lazily on first access, like in [`RegistryWheelIndex`], but queries take
+/// `&self`, so threads can look up different packages concurrently without external locking.
+#[derive(Debug)]
+pub struct SharedRegistryWheelIndex<'a> {
+    cache: &'a Cache,
+    tags: &'a Tags,
+    index_locations: &'a IndexLocations,
+    hasher: &'a HashStrategy,
+    /// Skip cache pointers that were written longer ago than this.
+    max_age: Option<Duration>,
+    /// Keep yanked wheels, marked with [`IndexEntry::yanked`].
+    include_yanked: bool,
+    /// The entries of each package, in reverse order, so the best wheel comes first.
+    index: DashMap<&'a PackageName, Arc<[IndexEntry]>, FxBuildHasher>,
+    /// Packages indexed from a single index they are pinned to.
+    pinned: DashMap<(&'a PackageName, &'a IndexUrl), Arc<[IndexEntry]>, FxBuildHasher>,
+}
+
+impl<'a> SharedRegistryWheelIndex<'a> {
+    /// Initialize an index of registry distributions, like [`RegistryWheelIndex::new`].
+    pub fn new(
+        cache: &'a Cache,
+        tags: &'a Tags,
+        index_locations: &'a IndexLocations,
+        hasher: &'a HashStrategy,
+        max_age: Option<Duration>,
+    ) -> Self {
+        Self {
+            cache,
+            tags,
+            index_locations,
+            hasher,
+            max_age,
+            include_yanked: false,
+            index: DashMap::default(),
+            pinned: DashMap::default(),
+        }
+    }
+
+    /// Include yanked wheels, like [`RegistryWheelIndex::with_yanked`].
+    #[must_use]
+    pub fn with_yanked(self, include_yanked: bool) -> Self {
+        Self {
+            include_yanked,
+            ..self
+        }
+    }
+
+    /// Return the available wheels for a given package, best first, like
+    /// [`RegistryWheelIndex::get`].
+    ///
+    /// If the package is not yet indexed, this will index the package by reading from the cache.
+    pub fn get(&self, name: &'a PackageName) -> Arc<[IndexEntry]> {
+        if let Some(entries) = self.index.get(name) {
+            return entries.clone();
+        }
+        let entries = self.index_reversed(name, None);
+        self.index.entry(name).or_insert(entries).clone()
+    }
+
+    /// Return the available wheels for a given package, best first, reading only the cache of
+    /// the index the package is pinned to, like [`RegistryWheelIndex::get_from`].
+    ///
+    /// If the package is not yet indexed for this index, this will index the package by reading
+    /// from the cache.
+    pub fn get_from(&self, name: &'a PackageName, index_url: &'a IndexUrl) -> Arc<[IndexEntry]> {
+        if let Some(entries) = self.pinned.get(&(name, index_url)) {
+            return entries.clone();
+        }
+        let entries = self.index_reversed(name, Some(index_url));
+        self.pinned
+            .entry((name, index_url))
+            .or_insert(entries)
+            .clone()
+    }
+
+    /// Index a package, without holding a lock on the map while reading from the cache.
+    ///
+    /// If two threads index the same package at the same time, both read the cache and the
+    /// first result is kept, which is the same as the second.
+    fn index_reversed(&self, name: &PackageName, pinned: Option<&IndexUrl>) -> Arc<[IndexEntry]> {
+        let mut entries = RegistryWheelIndex::index(
+            name,
+            self.cache,
+            self.tags,
+            self.index_locations,
+            pinned,
+            self.hasher,
+            self.max_age,
+            self.include_yanked,
+        );
+        entries.reverse();
+        entries.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::path::Path;
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+
+    use distribution_types::FlatIndexLocation;
+    use pep508_rs::VerbatimUrl;
+    use tracing_subscriber::fmt::MakeWriter;
+    use uv_cache::ArchiveId;
+    use uv_cache_info::Timestamp;
+
+    use crate::archive::Archive;
+
+    use super::*;
+
+    /// Collect the formatted trace events into a shared buffer.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Capture {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    /// A `--find-links` directory, which is cached like a local registry.
+    fn find_links(path: &Path) -> FlatIndexLocation {
+        FlatIndexLocation::Path(VerbatimUrl::from_absolute_path(path).unwrap())
+    }
+
+    /// Cache a wheel pointer for the given index, with the given contents.
+    fn cache_pointer(cache: &Cache, index_url: &IndexUrl, filename: &str, pointer: &[u8]) {
+        let wheel_dir = cache.shard(
+            CacheBucket::Wheels,
+            WheelCache::Index(index_url).wheel_dir("foo"),
+        );
+        fs_err::create_dir_all(&*wheel_dir).unwrap();
+        fs_err::write(wheel_dir.join(filename), pointer).unwrap();
+    }
+
+    /// A `.rev` pointer, encoded like a `LocalArchivePointer`.
+    fn local_pointer() -> Vec<u8> {
+        rmp_serde::to_vec(&(Timestamp::now(), Archive::new(ArchiveId::new(), vec![]))).unwrap()
+    }
+
+    /// A `.rev` pointer to a wheel that was yanked when it was downloaded.
+    fn yanked_pointer() -> Vec<u8> {
+        let archive = Archive::new(ArchiveId::new(), vec![]).with_yanked(true);
+        rmp_serde::to_vec(&(Timestamp::now(), archive)).unwrap()
+    }
+
+    /// Run the closure, returning its result and the trace events it emitted.
+    fn capture_trace<T>(f: impl FnOnce() -> T) -> (T, String) {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer(capture.clone())
+            .with_ansi(false)
+            .without_time()
+            .with_target(false)
+            .with_level(false)
+            .finish();
+        let result = tracing::subscriber::with_default(subscriber, f);
+        let trace = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        (result, trace)
+    }
+
+    fn py3_none_any() -> Tags {
+        Tags::new(vec![(
+            "py3".to_string(),
+            "none".to_string(),
+            "any".to_string(),
+        )])
+    }
+
+    #[test]
+    fn index_trace() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache = Cache::from_path(temp.path().join("cache"));
+        let index_locations = IndexLocations::new(
+            None,
+            vec![],
+            vec![find_links(&temp.path().join("find-links"))],
+            true,
+        );
+        let tags = py3_none_any();
+        let hasher = HashStrategy::None;
+
+        // Cache a compatible wheel, an incompatible wheel and a corrupt pointer.
+        let index_url = IndexUrl::from(index_locations.flat_index().next().unwrap().clone());
+        let pointer = local_pointer();
+        cache_pointer(&cache, &index_url, "foo-1.0.0-py3-none-any.rev", &pointer);
+        cache_pointer(
+            &cache,
+            &index_url,
+            "foo-1.1.0-cp312-cp312-win_amd64.rev",
+            &pointer,
+        );
+        cache_pointer(&cache, &index_url, "foo-1.2.0-py3-none-any.rev", b"corrupt");
+        let wheel_dir = cache.shard(
+            CacheBucket::Wheels,
+            WheelCache::Index(&index_url).wheel_dir("foo"),
+        );
+
+        let package = PackageName::from_str("foo").unwrap();
+        let (wheels, trace) = capture_trace(|| {
+            let mut index = RegistryWheelIndex::new(&cache, &tags, &index_locations, &hasher, None);
+            index
+                .get(&package)
+                .map(|entry| entry.dist.filename.to_string())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(wheels, ["foo-1.0.0-py3-none-any.whl"]);
+
+        let lines: Vec<&str> = trace.lines().map(str::trim).collect();
+        for expected in [
+            format!("Scanning cache directory: {}", wheel_dir.display()),
+            format!(
+                "Reading wheel pointer: {}",
+                wheel_dir.join("foo-1.0.0-py3-none-any.rev").display()
+            ),
+            "Accepting wheel: foo-1.0.0-py3-none-any.whl".to_string(),
+            "Rejecting wheel with incompatible tags: foo-1.1.0-cp312-cp312-win_amd64.whl"
+                .to_string(),
+            format!(
+                "Skipping unreadable wheel pointer: {}",
+                wheel_dir.join("foo-1.2.0-py3-none-any.rev").display()
+            ),
+        ] {
+            assert!(
+                lines.contains(&expected.as_str()),
+                "Missing `{expected}` in:\n{trace}"
+            );
+        }
+    }
+
+    #[test]
+    fn max_age() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache = Cache::from_path(temp.path().join("cache"));
+        let index_locations = IndexLocations::new(
+            None,
+            vec![],
+            vec![find_links(&temp.path().join("find-links"))],
+            true,
+        );
+        let tags = py3_none_any();
+        let hasher = HashStrategy::None;
+
+        let index_url = IndexUrl::from(index_locations.flat_index().next().unwrap().clone());
+        let pointer = local_pointer();
+        cache_pointer(&cache, &index_url, "foo-1.0.0-py3-none-any.rev", &pointer);
+        cache_pointer(&cache, &index_url, "foo-2.0.0-py3-none-any.rev", &pointer);
+
+        // Simulate a pointer that was written two days ago.
+        let wheel_dir = cache.shard(
+            CacheBucket::Wheels,
+            WheelCache::Index(&index_url).wheel_dir("foo"),
+        );
+        let old = wheel_dir.join("foo-2.0.0-py3-none-any.rev");
+        std::fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - Duration::from_secs(2 * 24 * 60 * 60))
+            .unwrap();
+
+        let package = PackageName::from_str("foo").unwrap();
+        let wheels = |max_age| {
+            let mut index =
+                RegistryWheelIndex::new(&cache, &tags, &index_locations, &hasher, max_age);
+            index
+                .get(&package)
+                .map(|entry| entry.dist.filename.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            wheels(None),
+            ["foo-2.0.0-py3-none-any.whl", "foo-1.0.0-py3-none-any.whl"]
+        );
+        assert_eq!(
+            wheels(Some(Duration::from_secs(24 * 60 * 60))),
+            ["foo-1.0.0-py3-none-any.whl"]
+        );
+    }
+
+    #[test]
+    fn get_version() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache = Cache::from_path(temp.path().join("cache"));
+        let index_locations = IndexLocations::new(
+            None,
+            vec![],
+            vec![find_links(&temp.path().join("find-links"))],
+            true,
+        );
+        let tags = Tags::new(vec![
+            ("py312".to_string(), "none".to_string(), "any".to_string()),
+            ("py3".to_string(), "none".to_string(), "any".to_string()),
+        ]);
+        let hasher = HashStrategy::None;
+
+        let index_url = IndexUrl::from(index_locations.flat_index().next().unwrap().clone());
+        let pointer = local_pointer();
+        for filename in [
+            "foo-1.0.0-py3-none-any.rev",
+            "foo-2.0.0-py3-none-any.rev",
+            "foo-2.0.0-py312-none-any.rev",
+            "foo-3.0.0-py3-none-any.rev",
+        ] {
+            cache_pointer(&cache, &index_url, filename, &pointer);
+        }
+
+        let package = PackageName::from_str("foo").unwrap();
+        let mut index = RegistryWheelIndex::new(&cache, &tags, &index_locations, &hasher, None);
+        let version = |version: &str| Version::from_str(version).unwrap();
+        assert_eq!(
+            index
+                .get_version(&package, &version("2.0.0"))
+                .map(|dist| dist.filename.to_string()),
+            Some("foo-2.0.0-py312-none-any.whl".to_string())
+        );
+        assert_eq!(
+            index
+                .get_version(&package, &version("1.0.0"))
+                .map(|dist| dist.filename.to_string()),
+            Some("foo-1.0.0-py3-none-any.whl".to_string())
+        );
+        assert!(index.get_version(&package, &version("1.5.0")).is_none());
+        assert!(index.get_version(&package, &version("4.0.0")).is_none());
+
+        // The single version agrees with the best match of the full list.
+        let best = index
+            .get(&package)
+            .find(|entry| entry.dist.filename.version == version("3.0.0"))
+            .map(|entry| entry.dist.clone());
+        assert_eq!(
+            index.get_version(&package, &version("3.0.0")).cloned(),
+            best
+        );
+    }
+
+    #[test]
+    fn yanked() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache = Cache::from_path(temp.path().join("cache"));
+        let index_locations = IndexLocations::new(
+            None,
+            vec![],
+            vec![find_links(&temp.path().join("find-links"))],
+            true,
+        );
+        let tags = py3_none_any();
+        let hasher = HashStrategy::None;
+
+        let index_url = IndexUrl::from(index_locations.flat_index().next().unwrap().clone());
+        cache_pointer(
+            &cache,
+            &index_url,
+            "foo-1.0.0-py3-none-any.rev",
+            &local_pointer(),
+        );
+        cache_pointer(
+            &cache,
+            &index_url,
+            "foo-2.0.0-py3-none-any.rev",
+            &yanked_pointer(),
+        );
+
+        // Yanked wheels are skipped by default.
+        let package = PackageName::from_str("foo").unwrap();
+        let (wheels, trace) = capture_trace(|| {
+            let mut index = RegistryWheelIndex::new(&cache, &tags, &index_locations, &hasher, None);
+            index
+                .get(&package)
+                .map(|entry| (entry.dist.filename.to_string(), entry.yanked))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(wheels, [("foo-1.0.0-py3-none-any.whl".to_string(), false)]);
+        assert!(
+            trace.contains("Rejecting yanked wheel: foo-2.0.0-py3-none-any.whl"),
+            "Missing yanked wheel in:\n{trace}"
+        );
+
+        // When included, they are marked, so they can still be used for pinned versions.
+        let (wheels, trace) = capture_trace(|| {
+            let mut index = RegistryWheelIndex::new(&cache, &tags, &index_locations, &hasher, None)
+                .with_yanked(true);
+            index
+                .get(&package)
+                .map(|entry| (entry.dist.filename.to_string(), entry.yanked))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(
+            wheels,
+            [
+                ("foo-2.0.0-py3-none-any.whl".to_string(), true),
+                ("foo-1.0.0-py3-none-any.whl".to_string(), false)
+            ]
+        );
+        assert!(
+            trace.contains("Accepting yanked wheel: foo-2.0.0-py3-none-any.whl"),
+            "Missing yanked wheel in:\n{trace}"
+        );
+    }
+
+    #[test]
+    fn shared_index_threads() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache = Cache::from_path(temp.path().join("cache"));
+        let index_locations = IndexLocations::new(
+            None,
+            vec![],
+            vec![find_links(&temp.path().join("find-links"))],
+            true,
+        );
+        let tags = py3_none_any();
+        let hasher = HashStrategy::None;
+
+        let index_url = IndexUrl::from(index_locations.flat_index().next().unwrap().clone());
+        let pointer = local_pointer();
+        let packages: Vec<PackageName> = (0..8)
+            .map(|i| PackageName::from_str(&format!("pkg{i}")).unwrap())
+            .collect();
+        for package in &packages {
+            let wheel_dir = cache.shard(
+                CacheBucket::Wheels,
+                WheelCache::Index(&index_url).wheel_dir(package.to_string()),
+            );
+            fs_err::create_dir_all(&*wheel_dir).unwrap();
+            for version in ["1.0.0", "2.0.0"] {
+                fs_err::write(
+                    wheel_dir.join(format!("{package}-{version}-py3-none-any.rev")),
+                    &pointer,
+                )
+                .unwrap();
+            }
+        }
+
+        let index = SharedRegistryWheelIndex::new(&cache, &tags, &index_locations, &hasher, None);
+        std::thread::scope(|scope| {
+            for package in &packages {
+                let index = &index;
+                scope.spawn(move || {
+                    let wheels: Vec<String> = index
+                        .get(package)
+                        .iter()
+                        .map(|entry| entry.dist.filename.to_string())
+                        .collect();
+                    assert_eq!(
+                        wheels,
+                        [
+                            format!("{package}-2.0.0-py3-none-any.whl"),
+                            format!("{package}-1.0.0-py3-none-any.whl")
+                        ]
+                    );
+                });
+            }
+        });
+        assert_eq!(index.index.len(), packages.len());
+
+        // The shared index agrees with the single-threaded index.
+        let mut single = RegistryWheelIndex::new(&cache, &tags, &index_locations, &hasher, None);
+        for package in &packages {
+            assert!(single.get(package).eq(index.get(package).iter()));
+        }
+    }
+
+    #[test]
+    fn pinned_index() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache = Cache::from_path(temp.path().join("cache"));
+        let index_locations = IndexLocations::new(
+            None,
+            vec![],
+            vec![
+                find_links(&temp.path().join("first")),
+                find_links(&temp.path().join("second")),
+            ],
+            true,
+        );
+        let tags = py3_none_any();
+        let hasher = HashStrategy::None;
+
+        let index_urls: Vec<IndexUrl> = index_locations
+            .flat_index()
+            .map(|flat_index| IndexUrl::from(flat_index.clone()))
+            .collect();
+        let pointer = local_pointer();
+        cache_pointer(
+            &cache,
+            &index_urls[0],
+            "foo-1.0.0-py3-none-any.rev",
+            &pointer,
+        );
+        cache_pointer(
+            &cache,
+            &index_urls[1],
+            "foo-2.0.0-py3-none-any.rev",
+            &pointer,
+        );
+
+        let package = PackageName::from_str("foo").unwrap();
+        let (wheels, trace) = capture_trace(|| {
+            let mut index = RegistryWheelIndex::new(&cache, &tags, &index_locations, &hasher, None);
+            index
+                .get_from(&package, &index_urls[0])
+                .map(|entry| entry.dist.filename.to_string())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(wheels, ["foo-1.0.0-py3-none-any.whl"]);
+
+        // The cache of the other index is not read.
+        let other = cache.shard(
+            CacheBucket::Wheels,
+            WheelCache::Index(&index_urls[1]).wheel_dir("foo"),
+        );
+        assert!(
+            !trace.contains(&other.display().to_string()),
+            "Unexpected read of `{}` in:\n{trace}",
+            other.display()
+        );
+    }
 }
@@ -1,7 +1,7 @@
 pub use distribution_database::{DistributionDatabase, HttpArchivePointer, LocalArchivePointer};
 pub use download::LocalWheel;
 pub use error::Error;
-pub use index::{BuiltWheelIndex, RegistryWheelIndex};
+pub use index::{BuiltWheelIndex, RegistryWheelIndex, SharedRegistryWheelIndex};
 pub use metadata::{ArchiveMetadata, LoweredRequirement, Metadata, RequiresDist};
 pub use reporter::Reporter;
 pub use source::prune;
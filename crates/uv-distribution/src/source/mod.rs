@@ -10,7 +10,6 @@ use crate::error::Error;
 use crate::metadata::{ArchiveMetadata, Metadata};
 use crate::reporter::Facade;
 use crate::source::built_wheel_metadata::BuiltWheelMetadata;
-use crate::source::revision::Revision;
 use crate::{Reporter, RequiresDist};
 use distribution_filename::{SourceDistExtension, WheelFilename};
 use distribution_types::{
@@ -37,6 +36,8 @@ use uv_metadata::read_archive_metadata;
 use uv_types::{BuildContext, SourceBuildTrait};
 use zip::ZipArchive;
 
+pub(crate) use revision::Revision;
+
 mod built_wheel_metadata;
 mod revision;
 
@@ -652,7 +653,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     .download_archive(response, source, filename, ext, entry.path(), hashes)
                     .await?;
 
-                Ok(revision.with_hashes(hashes))
+                Ok(revision.with_hashes(hashes).with_yanked(source.is_yanked()))
             }
             .boxed_local()
             .instrument(info_span!("download", source_dist = %source))
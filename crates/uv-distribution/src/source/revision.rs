@@ -14,6 +14,11 @@ use pypi_types::HashDigest;
 pub(crate) struct Revision {
     id: RevisionId,
     hashes: Vec<HashDigest>,
+    /// Whether the source distribution was yanked from the registry when it was downloaded.
+    ///
+    /// Like for wheels, the yank status is fixed at download time and not updated later.
+    #[serde(default)]
+    yanked: bool,
 }
 
 impl Revision {
@@ -22,6 +27,7 @@ impl Revision {
         Self {
             id: RevisionId::new(),
             hashes: vec![],
+            yanked: false,
         }
     }
 
@@ -46,6 +52,18 @@ impl Revision {
         self.hashes = hashes;
         self
     }
+
+    /// Return whether the source distribution was yanked from the registry.
+    pub(crate) fn yanked(&self) -> bool {
+        self.yanked
+    }
+
+    /// Set whether the source distribution was yanked from the registry.
+    #[must_use]
+    pub(crate) fn with_yanked(mut self, yanked: bool) -> Self {
+        self.yanked = yanked;
+        self
+    }
 }
 
 impl Hashed for Revision {
@@ -2,8 +2,10 @@ use anyhow::{Context, Error, Result};
 use install_wheel_rs::{linker::LinkMode, Layout};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::convert;
+use std::path::PathBuf;
 use tokio::sync::oneshot;
 use tracing::instrument;
+use url::Url;
 
 use distribution_types::CachedDist;
 use uv_cache::Cache;
@@ -15,6 +17,7 @@ pub struct Installer<'a> {
     cache: Option<&'a Cache>,
     reporter: Option<Box<dyn Reporter>>,
     installer_name: Option<String>,
+    direct_url_base: Option<PathBuf>,
 }
 
 impl<'a> Installer<'a> {
@@ -26,6 +29,7 @@ impl<'a> Installer<'a> {
             cache: None,
             reporter: None,
             installer_name: Some("uv".to_string()),
+            direct_url_base: None,
         }
     }
 
@@ -62,6 +66,19 @@ impl<'a> Installer<'a> {
         }
     }
 
+    /// Write the `file://` URLs in `direct_url.json` relative to this directory, e.g., for
+    /// development containers that mount the project at different locations.
+    ///
+    /// The directory is recorded as `base_url` next to the relative URL. By default, the URLs are
+    /// absolute, as required by the direct URL data structure.
+    #[must_use]
+    pub fn with_direct_url_base(self, direct_url_base: Option<PathBuf>) -> Self {
+        Self {
+            direct_url_base,
+            ..self
+        }
+    }
+
     /// Install a set of wheels into a Python virtual environment.
     #[instrument(skip_all, fields(num_wheels = %wheels.len()))]
     pub async fn install(self, wheels: Vec<CachedDist>) -> Result<Vec<CachedDist>> {
@@ -71,6 +88,7 @@ impl<'a> Installer<'a> {
             link_mode,
             reporter,
             installer_name,
+            direct_url_base,
         } = self;
 
         if cache.is_some_and(Cache::is_temporary) {
@@ -93,6 +111,7 @@ impl<'a> Installer<'a> {
                 link_mode,
                 reporter,
                 relocatable,
+                direct_url_base,
             );
 
             // This may fail if the main task was cancelled.
@@ -122,6 +141,7 @@ impl<'a> Installer<'a> {
             self.link_mode,
             self.reporter,
             self.venv.relocatable(),
+            self.direct_url_base,
         )
     }
 }
@@ -135,20 +155,34 @@ fn install(
     link_mode: LinkMode,
     reporter: Option<Box<dyn Reporter>>,
     relocatable: bool,
+    direct_url_base: Option<PathBuf>,
 ) -> Result<Vec<CachedDist>> {
+    let direct_url_base = direct_url_base
+        .map(|base| {
+            Url::from_directory_path(&base).map_err(|()| {
+                anyhow::anyhow!(
+                    "The base for `direct_url.json` must be an absolute path: `{}`",
+                    base.display()
+                )
+            })
+        })
+        .transpose()?;
     let locks = install_wheel_rs::linker::Locks::default();
     wheels.par_iter().try_for_each(|wheel| {
+        let mut direct_url = wheel
+            .parsed_url()?
+            .as_ref()
+            .map(pypi_types::DirectUrl::try_from)
+            .transpose()?;
+        if let (Some(base), Some(absolute)) = (&direct_url_base, &direct_url) {
+            direct_url = Some(absolute.relative_to(base)?);
+        }
         install_wheel_rs::linker::install_wheel(
             &layout,
             relocatable,
             wheel.path(),
             wheel.filename(),
-            wheel
-                .parsed_url()?
-                .as_ref()
-                .map(pypi_types::DirectUrl::try_from)
-                .transpose()?
-                .as_ref(),
+            direct_url.as_ref(),
             if wheel.cache_info().is_empty() {
                 None
             } else {
@@ -8,9 +8,10 @@ use tracing::debug;
 use distribution_filename::{DistExtension, WheelFilename};
 use distribution_types::{
     CachedDirectUrlDist, CachedDist, DirectUrlBuiltDist, DirectUrlSourceDist, DirectorySourceDist,
-    Error, GitSourceDist, Hashed, IndexLocations, InstalledDist, Name, PathBuiltDist,
+    Error, GitSourceDist, Hashed, IndexLocations, IndexUrl, InstalledDist, Name, PathBuiltDist,
     PathSourceDist, RemoteSource, Verbatim,
 };
+use pep440_rs::{Operator, VersionSpecifiers};
 use platform_tags::Tags;
 use pypi_types::{Requirement, RequirementSource, ResolverMarkerEnvironment};
 use uv_cache::{Cache, CacheBucket, WheelCache};
@@ -63,8 +64,23 @@ impl<'a> Planner<'a> {
         markers: &ResolverMarkerEnvironment,
         tags: &Tags,
     ) -> Result<Plan> {
-        // Index all the already-downloaded wheels in the cache.
-        let mut registry_index = RegistryWheelIndex::new(cache, tags, index_locations, hasher);
+        // The indexes that packages are pinned to, e.g., in `tool.uv.sources`. For those, only the
+        // cache of the pinned index is read.
+        let pinned_indexes: FxHashMap<&str, IndexUrl> = self
+            .requirements
+            .iter()
+            .filter_map(|requirement| match &requirement.source {
+                RequirementSource::Registry {
+                    index: Some(index), ..
+                } => Some((index.as_str(), IndexUrl::from_str(index).ok()?)),
+                _ => None,
+            })
+            .collect();
+
+        // Index all the already-downloaded wheels in the cache, including the yanked wheels, which
+        // may still be used for a pinned version.
+        let mut registry_index =
+            RegistryWheelIndex::new(cache, tags, index_locations, hasher, None).with_yanked(true);
         let built_index = BuiltWheelIndex::new(cache, tags, hasher, config_settings);
 
         let mut cached = vec![];
@@ -143,21 +159,31 @@ impl<'a> Planner<'a> {
 
             // Identify any cached distributions that satisfy the requirement.
             match &requirement.source {
-                RequirementSource::Registry { specifier, .. } => {
-                    if let Some(distribution) =
-                        registry_index.get(&requirement.name).find_map(|entry| {
-                            if !specifier.contains(&entry.dist.filename.version) {
-                                return None;
-                            };
-                            if entry.built && no_build {
-                                return None;
-                            }
-                            if !entry.built && no_binary {
-                                return None;
+                RequirementSource::Registry { specifier, index } => {
+                    let mut entries: Box<dyn Iterator<Item = _>> =
+                        match index.as_deref().and_then(|index| pinned_indexes.get(index)) {
+                            Some(index_url) => {
+                                Box::new(registry_index.get_from(&requirement.name, index_url))
                             }
-                            Some(&entry.dist)
-                        })
-                    {
+                            None => Box::new(registry_index.get(&requirement.name)),
+                        };
+                    if let Some(distribution) = entries.find_map(|entry| {
+                        if !specifier.contains(&entry.dist.filename.version) {
+                            return None;
+                        };
+                        // Like in the resolver, yanked wheels may only be used for pinned
+                        // requirements, e.g., `black==23.10.0`.
+                        if entry.yanked && !is_pinned(specifier) {
+                            return None;
+                        }
+                        if entry.built && no_build {
+                            return None;
+                        }
+                        if !entry.built && no_binary {
+                            return None;
+                        }
+                        Some(&entry.dist)
+                    }) {
                         debug!("Requirement already cached: {distribution}");
                         cached.push(CachedDist::Registry(distribution.clone()));
                         continue;
@@ -451,6 +477,14 @@ impl<'a> Planner<'a> {
     }
 }
 
+/// Returns `true` if the specifier pins a single version, e.g., `==23.10.0`.
+fn is_pinned(specifier: &VersionSpecifiers) -> bool {
+    let [specifier] = specifier.as_ref() else {
+        return false;
+    };
+    matches!(specifier.operator(), Operator::Equal | Operator::ExactEqual)
+}
+
 #[derive(Debug, Default)]
 pub struct Plan {
     /// The distributions that are not already installed in the current environment, but are
@@ -60,6 +60,7 @@ impl RequirementSatisfaction {
                     url: installed_url,
                     archive_info: _,
                     subdirectory: installed_subdirectory,
+                    base_url: _,
                 } = direct_url.as_ref()
                 else {
                     return Ok(Self::Mismatch);
@@ -166,6 +167,7 @@ impl RequirementSatisfaction {
                     url: installed_url,
                     archive_info: _,
                     subdirectory: None,
+                    base_url: _,
                 } = direct_url.as_ref()
                 else {
                     return Ok(Self::Mismatch);
@@ -218,6 +220,7 @@ impl RequirementSatisfaction {
                         DirInfo {
                             editable: installed_editable,
                         },
+                    base_url: _,
                 } = direct_url.as_ref()
                 else {
                     return Ok(Self::Mismatch);
@@ -55,6 +55,13 @@ pub enum ResolveError {
     #[error("Package `{0}` attempted to resolve via URL: {1}. URL dependencies must be expressed as direct requirements or constraints. Consider adding `{0} @ {1}` to your dependencies or constraints file.")]
     DisallowedUrl(PackageName, String),
 
+    #[error("Package `{package_name}` was resolved from `{index}`, which is not one of its allowed indexes:\n- {}", allowed.join("\n- "))]
+    DisallowedIndex {
+        package_name: PackageName,
+        index: String,
+        allowed: Vec<String>,
+    },
+
     #[error(transparent)]
     DistributionType(#[from] distribution_types::Error),
 
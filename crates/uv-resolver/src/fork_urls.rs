@@ -11,12 +11,33 @@ use crate::ResolveError;
 
 /// See [`crate::resolver::SolveState`].
 #[derive(Default, Debug, Clone)]
-pub(crate) struct ForkUrls(FxHashMap<PackageName, VerbatimParsedUrl>);
+pub(crate) struct ForkUrls(FxHashMap<PackageName, ForkUrl>);
+
+/// A URL used for a package in a fork, with the package that requested it.
+#[derive(Debug, Clone)]
+struct ForkUrl {
+    url: VerbatimParsedUrl,
+    /// The dependent package, e.g., `foo==1.0.0`, or `None` for the direct requirements.
+    requested_by: Option<String>,
+}
+
+impl ForkUrl {
+    /// The URL and the package that requested it, for error messages.
+    fn describe(&self) -> String {
+        match &self.requested_by {
+            Some(package) => format!(
+                "{} (requested by `{package}`)",
+                self.url.verbatim.verbatim()
+            ),
+            None => self.url.verbatim.verbatim().to_string(),
+        }
+    }
+}
 
 impl ForkUrls {
     /// Get the URL previously used for a package in this fork.
     pub(crate) fn get(&self, package_name: &PackageName) -> Option<&VerbatimParsedUrl> {
-        self.0.get(package_name)
+        self.0.get(package_name).map(|fork_url| &fork_url.url)
     }
 
     /// Whether we use a URL for this package.
@@ -25,19 +46,24 @@ impl ForkUrls {
     }
 
     /// Check that this is the only URL used for this package in this fork.
+    ///
+    /// `requested_by` is the package whose dependencies contain the URL, or `None` for the direct
+    /// requirements, so that a conflict can point to both origins.
     pub(crate) fn insert(
         &mut self,
         package_name: &PackageName,
         url: &VerbatimParsedUrl,
+        requested_by: Option<&str>,
         fork_markers: &ResolverMarkers,
     ) -> Result<(), ResolveError> {
+        let fork_url = ForkUrl {
+            url: url.clone(),
+            requested_by: requested_by.map(ToString::to_string),
+        };
         match self.0.entry(package_name.clone()) {
             Entry::Occupied(previous) => {
-                if previous.get() != url {
-                    let mut conflicting_url = vec![
-                        previous.get().verbatim.verbatim().to_string(),
-                        url.verbatim.verbatim().to_string(),
-                    ];
+                if previous.get().url != *url {
+                    let mut conflicting_url = vec![previous.get().describe(), fork_url.describe()];
                     conflicting_url.sort();
                     return match fork_markers {
                         ResolverMarkers::Universal { .. }
@@ -58,9 +84,47 @@ impl ForkUrls {
                 }
             }
             Entry::Vacant(vacant) => {
-                vacant.insert(url.clone());
+                vacant.insert(fork_url);
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep508_rs::{MarkerTree, Pep508Url};
+
+    use super::*;
+
+    #[test]
+    fn conflicting_urls_fork() {
+        let name = PackageName::from_str("foo").unwrap();
+        let a =
+            VerbatimParsedUrl::parse_url("https://example.com/a/foo-1.0.0-py3-none-any.whl", None)
+                .unwrap();
+        let b =
+            VerbatimParsedUrl::parse_url("https://example.com/b/foo-1.0.0-py3-none-any.whl", None)
+                .unwrap();
+        let markers =
+            ResolverMarkers::Fork(MarkerTree::from_str("sys_platform == 'linux'").unwrap());
+
+        let mut fork_urls = ForkUrls::default();
+        fork_urls.insert(&name, &a, None, &markers).unwrap();
+        // The same URL from another package is not a conflict.
+        fork_urls
+            .insert(&name, &a, Some("bar==1.0.0"), &markers)
+            .unwrap();
+        let err = fork_urls
+            .insert(&name, &b, Some("baz==2.0.0"), &markers)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Requirements contain conflicting URLs for package `foo` in split `sys_platform == 'linux'`:\n\
+            - https://example.com/a/foo-1.0.0-py3-none-any.whl\n\
+            - https://example.com/b/foo-1.0.0-py3-none-any.whl (requested by `baz==2.0.0`)"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use rustc_hash::FxHashMap;
+
+use distribution_types::IndexUrl;
+use uv_normalize::PackageName;
+
+use crate::ResolveError;
+
+/// The indexes that a package may be resolved from.
+///
+/// Packages without an entry may come from any index.
+#[derive(Debug, Default, Clone)]
+pub struct IndexAllowlist(FxHashMap<PackageName, Vec<IndexUrl>>);
+
+impl IndexAllowlist {
+    /// Check that the package is allowed to be resolved from the given index.
+    pub(crate) fn check(
+        &self,
+        package_name: &PackageName,
+        index: &IndexUrl,
+    ) -> Result<(), ResolveError> {
+        let Some(allowed) = self.0.get(package_name) else {
+            return Ok(());
+        };
+        if allowed.contains(index) {
+            return Ok(());
+        }
+        Err(ResolveError::DisallowedIndex {
+            package_name: package_name.clone(),
+            index: index.to_string(),
+            allowed: allowed.iter().map(ToString::to_string).collect(),
+        })
+    }
+}
+
+impl FromIterator<(PackageName, Vec<IndexUrl>)> for IndexAllowlist {
+    fn from_iter<T: IntoIterator<Item = (PackageName, Vec<IndexUrl>)>>(iter: T) -> Self {
+        let mut allowlist = FxHashMap::<PackageName, Vec<IndexUrl>>::default();
+        for (package_name, indexes) in iter {
+            allowlist.entry(package_name).or_default().extend(indexes);
+        }
+        Self(allowlist)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn allowed() {
+        let internal = IndexUrl::from_str("https://internal.example.com/simple").unwrap();
+        let allowlist = IndexAllowlist::from_iter([(
+            PackageName::from_str("foo").unwrap(),
+            vec![internal.clone()],
+        )]);
+
+        allowlist
+            .check(&PackageName::from_str("foo").unwrap(), &internal)
+            .unwrap();
+        // Packages without an entry are unconstrained.
+        allowlist
+            .check(
+                &PackageName::from_str("bar").unwrap(),
+                &IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn disallowed() {
+        let internal = IndexUrl::from_str("https://internal.example.com/simple").unwrap();
+        let allowlist =
+            IndexAllowlist::from_iter([(PackageName::from_str("foo").unwrap(), vec![internal])]);
+
+        let err = allowlist
+            .check(
+                &PackageName::from_str("foo").unwrap(),
+                &IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Package `foo` was resolved from `https://pypi.org/simple`, which is not one of its allowed indexes:\n- https://internal.example.com/simple"
+        );
+    }
+}
@@ -3,6 +3,7 @@ pub use error::{NoSolutionError, NoSolutionHeader, ResolveError};
 pub use exclude_newer::ExcludeNewer;
 pub use exclusions::Exclusions;
 pub use flat_index::FlatIndex;
+pub use index_allowlist::IndexAllowlist;
 pub use lock::{
     Lock, LockError, RequirementsTxtExport, ResolverManifest, SatisfiesResult, TreeDisplay,
 };
@@ -36,6 +37,7 @@ mod exclusions;
 mod flat_index;
 mod fork_urls;
 mod graph_ops;
+mod index_allowlist;
 mod lock;
 mod manifest;
 mod marker;
@@ -9,7 +9,7 @@ use uv_normalize::{GroupName, PackageName};
 use uv_types::RequestedRequirements;
 
 use crate::preferences::Preferences;
-use crate::{DependencyMode, Exclusions, ResolverMarkers};
+use crate::{DependencyMode, Exclusions, IndexAllowlist, ResolverMarkers};
 
 /// A manifest of requirements, constraints, and preferences.
 #[derive(Clone, Debug)]
@@ -52,6 +52,9 @@ pub struct Manifest {
     /// determinations around "allowed" versions (for example, "allowed" URLs or "allowed"
     /// pre-release versions).
     pub(crate) lookaheads: Vec<RequestedRequirements>,
+
+    /// The indexes that individual packages are allowed to be resolved from.
+    pub(crate) index_allowlist: IndexAllowlist,
 }
 
 impl Manifest {
@@ -76,6 +79,7 @@ impl Manifest {
             workspace_members: workspace_members.unwrap_or_default(),
             exclusions,
             lookaheads,
+            index_allowlist: IndexAllowlist::default(),
         }
     }
 
@@ -90,6 +94,7 @@ impl Manifest {
             exclusions: Exclusions::default(),
             workspace_members: BTreeSet::new(),
             lookaheads: Vec::new(),
+            index_allowlist: IndexAllowlist::default(),
         }
     }
 
@@ -99,6 +104,12 @@ impl Manifest {
         self
     }
 
+    #[must_use]
+    pub fn with_index_allowlist(mut self, index_allowlist: IndexAllowlist) -> Self {
+        self.index_allowlist = index_allowlist;
+        self
+    }
+
     /// Return an iterator over all requirements, constraints, and overrides, in priority order,
     /// such that requirements come first, followed by constraints, followed by overrides.
     ///
@@ -1,8 +1,8 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Display, Formatter};
 
 use distribution_types::{
-    Dist, DistributionMetadata, Name, ResolutionDiagnostic, ResolvedDist, VersionId,
+    Dist, DistributionMetadata, IndexUrl, Name, ResolutionDiagnostic, ResolvedDist, VersionId,
     VersionOrUrlRef,
 };
 use indexmap::IndexSet;
@@ -542,6 +542,21 @@ impl ResolutionGraph {
         self.dists().any(|dist| dist.name() == name)
     }
 
+    /// Return the index each registry package was resolved from, e.g., to audit which packages
+    /// came from an internal index and which from a public one.
+    ///
+    /// A package can have multiple indexes if the forks resolved it from different indexes.
+    /// Packages from URLs, paths and installed distributions are omitted.
+    pub fn indexes(&self) -> BTreeMap<&PackageName, BTreeSet<&IndexUrl>> {
+        let mut indexes: BTreeMap<&PackageName, BTreeSet<&IndexUrl>> = BTreeMap::new();
+        for dist in self.dists() {
+            if let Some(index) = dist.dist.index() {
+                indexes.entry(dist.name()).or_default().insert(index);
+            }
+        }
+        indexes
+    }
+
     /// Return the [`ResolutionDiagnostic`]s that were encountered while building the graph.
     pub fn diagnostics(&self) -> &[ResolutionDiagnostic] {
         &self.diagnostics
@@ -875,3 +890,106 @@ fn has_lower_bound(
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use distribution_filename::SourceDistExtension;
+    use distribution_types::{File, FileLocation, RegistrySourceDist, SourceDist};
+
+    use super::*;
+
+    /// A node for the source distribution of `name` 1.0.0 from `index`.
+    fn registry_node(name: &str, index: &IndexUrl) -> ResolutionGraphNode {
+        let name = PackageName::from_str(name).unwrap();
+        let version = Version::from_str("1.0.0").unwrap();
+        let filename = format!("{name}-{version}.tar.gz");
+        let file = File {
+            dist_info_metadata: false,
+            filename: filename.clone(),
+            hashes: vec![],
+            requires_python: None,
+            size: None,
+            upload_time_utc_ms: None,
+            url: FileLocation::RelativeUrl(index.to_string(), filename),
+            yanked: None,
+        };
+        let dist = Dist::Source(SourceDist::Registry(RegistrySourceDist {
+            name: name.clone(),
+            version: version.clone(),
+            file: Box::new(file),
+            ext: SourceDistExtension::TarGz,
+            index: index.clone(),
+            wheels: vec![],
+        }));
+        ResolutionGraphNode::Dist(AnnotatedDist {
+            dist: ResolvedDist::Installable(dist),
+            name,
+            version,
+            extra: None,
+            dev: None,
+            hashes: vec![],
+            metadata: None,
+            marker: MarkerTree::TRUE,
+        })
+    }
+
+    #[test]
+    fn indexes() {
+        let internal = IndexUrl::from_str("https://internal.example.com/simple").unwrap();
+        let mirror = IndexUrl::from_str("https://mirror.example.com/simple").unwrap();
+
+        let mut petgraph = Graph::new();
+        petgraph.add_node(ResolutionGraphNode::Root);
+        petgraph.add_node(registry_node("foo", &internal));
+        petgraph.add_node(registry_node("bar", &mirror));
+        // A package resolved from different indexes in different forks.
+        petgraph.add_node(registry_node("baz", &internal));
+        petgraph.add_node(registry_node("baz", &mirror));
+        let graph = ResolutionGraph {
+            petgraph,
+            requires_python: RequiresPython::greater_than_equal_version(
+                &Version::from_str("3.8").unwrap(),
+            ),
+            fork_markers: vec![],
+            diagnostics: vec![],
+            requirements: vec![],
+            constraints: Constraints::default(),
+            overrides: Overrides::default(),
+            options: Options::default(),
+            package_markers: FxHashMap::default(),
+        };
+
+        let indexes: Vec<(String, Vec<String>)> = graph
+            .indexes()
+            .into_iter()
+            .map(|(name, indexes)| {
+                (
+                    name.to_string(),
+                    indexes.into_iter().map(ToString::to_string).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            indexes,
+            [
+                (
+                    "bar".to_string(),
+                    vec!["https://mirror.example.com/simple".to_string()]
+                ),
+                (
+                    "baz".to_string(),
+                    vec![
+                        "https://internal.example.com/simple".to_string(),
+                        "https://mirror.example.com/simple".to_string()
+                    ]
+                ),
+                (
+                    "foo".to_string(),
+                    vec!["https://internal.example.com/simple".to_string()]
+                ),
+            ]
+        );
+    }
+}
@@ -67,7 +67,7 @@ pub use crate::resolver::provider::{
 use crate::resolver::reporter::Facade;
 pub use crate::resolver::reporter::{BuildId, Reporter};
 use crate::yanks::AllowedYanks;
-use crate::{marker, DependencyMode, Exclusions, FlatIndex, Options};
+use crate::{marker, DependencyMode, Exclusions, FlatIndex, IndexAllowlist, Options};
 
 mod availability;
 mod batch_prefetch;
@@ -99,6 +99,7 @@ struct ResolverState<InstalledPackages: InstalledPackagesProvider> {
     exclusions: Exclusions,
     urls: Urls,
     locals: Locals,
+    index_allowlist: IndexAllowlist,
     dependency_mode: DependencyMode,
     hasher: HashStrategy,
     markers: ResolverMarkers,
@@ -202,6 +203,7 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
             urls: Urls::from_manifest(&manifest, &markers, git, options.dependency_mode)?,
             locals: Locals::from_manifest(&manifest, &markers, options.dependency_mode),
             groups: Groups::from_manifest(&manifest, &markers),
+            index_allowlist: manifest.index_allowlist,
             project: manifest.project,
             workspace_members: manifest.workspace_members,
             requirements: manifest.requirements,
@@ -1090,6 +1092,16 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
             )));
         }
 
+        // The package may be restricted to specific indexes.
+        let index = match dist.for_installation() {
+            ResolvedDistRef::InstallableRegistrySourceDist { sdist, .. } => Some(&sdist.index),
+            ResolvedDistRef::InstallableRegistryBuiltDist { wheel, .. } => Some(&wheel.index),
+            ResolvedDistRef::Installed(_) => None,
+        };
+        if let Some(index) = index {
+            self.index_allowlist.check(name, index)?;
+        }
+
         let filename = match dist.for_installation() {
             ResolvedDistRef::InstallableRegistrySourceDist { sdist, .. } => sdist
                 .filename()
@@ -2127,7 +2139,8 @@ impl ForkState {
                 // override URLs to both URL and registry requirements, which we then check for
                 // conflicts using [`ForkUrl`].
                 if let Some(url) = urls.get_url(name, url.as_ref(), git)? {
-                    self.fork_urls.insert(name, url, &self.markers)?;
+                    self.fork_urls
+                        .insert(name, url, for_package, &self.markers)?;
                     has_url = true;
                 };
 
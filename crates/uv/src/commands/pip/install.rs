@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anstream::eprint;
 use itertools::Itertools;
@@ -81,6 +82,7 @@ pub(crate) async fn pip_install(
     native_tls: bool,
     cache: Cache,
     dry_run: bool,
+    direct_url_base: Option<&Path>,
     printer: Printer,
 ) -> anyhow::Result<ExitStatus> {
     let start = std::time::Instant::now();
@@ -423,6 +425,7 @@ pub(crate) async fn pip_install(
         &environment,
         Box::new(DefaultInstallLogger),
         dry_run,
+        direct_url_base,
         printer,
     )
     .await?;
@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anstream::eprint;
 use anyhow::Result;
@@ -70,6 +71,7 @@ pub(crate) async fn pip_sync(
     native_tls: bool,
     cache: Cache,
     dry_run: bool,
+    direct_url_base: Option<&Path>,
     printer: Printer,
 ) -> Result<ExitStatus> {
     let client_builder = BaseClientBuilder::new()
@@ -374,6 +376,7 @@ pub(crate) async fn pip_sync(
         &environment,
         Box::new(DefaultInstallLogger),
         dry_run,
+        direct_url_base,
         printer,
     )
     .await?;
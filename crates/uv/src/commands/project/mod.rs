@@ -1016,6 +1016,7 @@ pub(crate) async fn sync_environment(
         &venv,
         logger,
         dry_run,
+        None,
         printer,
     )
     .await?;
@@ -1252,6 +1253,7 @@ pub(crate) async fn update_environment(
         &venv,
         install,
         dry_run,
+        None,
         printer,
     )
     .await?;
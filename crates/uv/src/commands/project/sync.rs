@@ -346,6 +346,7 @@ pub(super) async fn do_sync(
         venv,
         logger,
         dry_run,
+        None,
         printer,
     )
     .await?;
@@ -417,6 +417,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 globals.native_tls,
                 cache,
                 args.dry_run,
+                args.direct_url_base.as_deref(),
                 printer,
             )
             .await
@@ -504,6 +505,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 globals.native_tls,
                 cache,
                 args.dry_run,
+                args.direct_url_base.as_deref(),
                 printer,
             )
             .await
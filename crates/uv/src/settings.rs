@@ -1213,6 +1213,7 @@ pub(crate) struct PipSyncSettings {
     pub(crate) constraint: Vec<PathBuf>,
     pub(crate) build_constraint: Vec<PathBuf>,
     pub(crate) dry_run: bool,
+    pub(crate) direct_url_base: Option<PathBuf>,
     pub(crate) refresh: Refresh,
     pub(crate) settings: PipSettings,
 }
@@ -1248,6 +1249,7 @@ impl PipSyncSettings {
             strict,
             no_strict,
             dry_run,
+            direct_url_base,
             compat_args: _,
         } = *args;
 
@@ -1262,6 +1264,7 @@ impl PipSyncSettings {
                 .filter_map(Maybe::into_option)
                 .collect(),
             dry_run,
+            direct_url_base,
             refresh: Refresh::from(refresh),
             settings: PipSettings::combine(
                 PipOptions {
@@ -1301,6 +1304,7 @@ pub(crate) struct PipInstallSettings {
     pub(crate) r#override: Vec<PathBuf>,
     pub(crate) build_constraint: Vec<PathBuf>,
     pub(crate) dry_run: bool,
+    pub(crate) direct_url_base: Option<PathBuf>,
     pub(crate) constraints_from_workspace: Vec<Requirement>,
     pub(crate) overrides_from_workspace: Vec<Requirement>,
     pub(crate) refresh: Refresh,
@@ -1344,6 +1348,7 @@ impl PipInstallSettings {
             strict,
             no_strict,
             dry_run,
+            direct_url_base,
             compat_args: _,
         } = args;
 
@@ -1392,6 +1397,7 @@ impl PipInstallSettings {
                 .filter_map(Maybe::into_option)
                 .collect(),
             dry_run,
+            direct_url_base,
             constraints_from_workspace,
             overrides_from_workspace,
             refresh: Refresh::from(refresh),
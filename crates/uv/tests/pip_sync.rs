@@ -1067,6 +1067,76 @@ fn warn_on_yanked_dry_run() -> Result<()> {
     Ok(())
 }
 
+/// Write the URL in `direct_url.json` relative to `--direct-url-base`.
+#[test]
+fn direct_url_base() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let archive = context.temp_dir.child("ok-1.0.0-py3-none-any.whl");
+    fs::copy(
+        context
+            .workspace_root
+            .join("scripts/links/ok-1.0.0-py3-none-any.whl"),
+        &archive,
+    )?;
+    let archive_url = Url::from_file_path(archive.path()).unwrap();
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(&format!("ok @ {archive_url}"))?;
+
+    let direct_url = || -> Result<serde_json::Value> {
+        let path = context
+            .site_packages()
+            .join("ok-1.0.0.dist-info")
+            .join("direct_url.json");
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    };
+
+    // By default, the URL is absolute.
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .assert()
+        .success();
+    let absolute = direct_url()?;
+    assert_eq!(absolute["url"], archive_url.as_str());
+    assert!(absolute.get("base_url").is_none());
+
+    // With a base, the URL is relative to it, and the base is recorded next to it.
+    context.reset_venv();
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .arg("--direct-url-base")
+        .arg(context.temp_dir.path())
+        .assert()
+        .success();
+    let relative = direct_url()?;
+    assert_eq!(relative["url"], "ok-1.0.0-py3-none-any.whl");
+    assert_eq!(
+        relative["base_url"],
+        Url::from_directory_path(context.temp_dir.path())
+            .unwrap()
+            .as_str()
+    );
+
+    // The relative URL is resolved against the base, so the package is still recognized.
+    uv_snapshot!(context.filters(), context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--direct-url-base")
+        .arg(context.temp_dir.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a local wheel.
 #[test]
 fn install_local_wheel() -> Result<()> {